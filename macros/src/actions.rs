@@ -0,0 +1,126 @@
+//! mod `actions` - the implementation of `struct_actions!`.
+
+use crate::expand::{assoc_type, nest, sibling};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use syn::{
+    braced, bracketed, parenthesized,
+    parse::{Parse, ParseStream},
+    Ident, Path, Token,
+};
+
+/// `$path<{$items}>` (a type) or `$path($items)` (an expression), preceded by the path of the
+/// `dioxus_shareables` crate.
+pub struct StructActions {
+    krate: TokenStream,
+    path: Path,
+    ty: bool,
+    /// The fields with their flags (`W`, `RW` or `R`.)
+    items: Vec<(Ident, Ident)>,
+}
+
+impl Parse for StructActions {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse::<TokenTree>()?.into();
+        let path = input.call(Path::parse_mod_style)?;
+        let inner;
+        let ty = if input.peek(Token![<]) {
+            input.parse::<Token![<]>()?;
+            braced!(inner in input);
+            input.parse::<Token![>]>()?;
+            true
+        } else {
+            parenthesized!(inner in input);
+            false
+        };
+        let mut items = Vec::new();
+        while !inner.is_empty() {
+            let expected = "expected `W[..]`, `RW[..]` or `R[..]`";
+            let flag = inner
+                .parse::<Ident>()
+                .map_err(|e| syn::Error::new(e.span(), expected))?;
+            if flag != "W" && flag != "RW" && flag != "R" {
+                return Err(syn::Error::new(flag.span(), expected));
+            }
+            let fields;
+            bracketed!(fields in inner);
+            while !fields.is_empty() {
+                items.push((flag.clone(), fields.parse()?));
+                if fields.peek(Token![,]) {
+                    fields.parse::<Token![,]>()?;
+                }
+            }
+        }
+        Ok(Self {
+            krate,
+            path,
+            ty,
+            items,
+        })
+    }
+}
+
+pub fn expand(def: &StructActions) -> TokenStream {
+    let StructActions { krate, path, .. } = def;
+    let actions_tr = sibling(path, false, |i| format_ident!("{i}Actions"));
+    let items = def
+        .items
+        .iter()
+        .map(|(flag, f)| {
+            let field = assoc_type(krate, &actions_tr, "Field", f);
+            if def.ty {
+                let ty = format_ident!("{flag}Type");
+                quote!(<#field as #krate::r#struct::FieldOf>::#ty)
+            } else {
+                // The named check reports an unknown field as such.
+                quote!({
+                    let () = <() as #actions_tr>::__FieldData::#f;
+                    <#field as #krate::r#struct::FieldOf>::#flag
+                })
+            }
+        })
+        .collect::<Vec<_>>();
+    nest(&items)
+}
+
+/// `$path::Actions::$action` or `$path::Fields::$field`, preceded by the path of the
+/// `dioxus_shareables` crate.
+pub struct AssocLookup {
+    krate: TokenStream,
+    path: Path,
+    kind: &'static str,
+    ident: Ident,
+}
+
+impl Parse for AssocLookup {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse::<TokenTree>()?.into();
+        let mut path = input.call(Path::parse_mod_style)?;
+        let expected = "expected `Struct::Actions::ACTION` or `Struct::Fields::field`";
+        let (ident, kind) = match (path.segments.pop(), path.segments.pop()) {
+            (Some(ident), Some(kind)) if !path.segments.is_empty() => {
+                (ident.into_value().ident, kind.into_value().ident)
+            }
+            _ => return Err(input.error(expected)),
+        };
+        let kind = if kind == "Actions" {
+            "Action"
+        } else if kind == "Fields" {
+            "Field"
+        } else {
+            return Err(syn::Error::new(kind.span(), expected));
+        };
+        path.segments.pop_punct();
+        Ok(Self {
+            krate,
+            path,
+            kind,
+            ident,
+        })
+    }
+}
+
+pub fn expand_lookup(def: &AssocLookup) -> TokenStream {
+    let actions_tr = sibling(&def.path, false, |i| format_ident!("{i}Actions"));
+    assoc_type(&def.krate, &actions_tr, def.kind, &def.ident)
+}
@@ -0,0 +1,220 @@
+//! mod `attr` - the attribute form of `shareable_struct!`.
+//!
+//! This turns a plain struct annotated with `#[shareable(..)]` attributes into the same
+//! [`StructDef`] the function-like macro parses.
+
+use crate::parse::{parse_sources, Action, Field, StructDef, Substruct};
+use proc_macro2::{Span, TokenStream};
+use proc_macro_crate::{crate_name, FoundCrate};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote, Attribute, Expr, Fields, Ident, ItemStruct, LitStr, Token, Type,
+};
+
+/// `$vis $ident$(: $vis $type)?$(impl $vis $trait)? = "$items"`
+struct ActionAttr(Action);
+impl Parse for ActionAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        let ident = input.parse()?;
+        let mut ty = None;
+        if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            ty = Some((input.parse()?, input.parse()?));
+        }
+        let mut tr = None;
+        if input.peek(Token![impl]) {
+            input.parse::<Token![impl]>()?;
+            tr = Some((input.parse()?, input.parse()?));
+        }
+        input.parse::<Token![=]>()?;
+        let mut action = Action {
+            vis,
+            ident,
+            ty,
+            tr,
+            w: Vec::new(),
+            rw: Vec::new(),
+            r: Vec::new(),
+            sub: Vec::new(),
+            refs: Vec::new(),
+        };
+        input.parse::<LitStr>()?.parse_with(|items: ParseStream| {
+            while !items.is_empty() {
+                action.parse_item(items)?;
+            }
+            Ok(())
+        })?;
+        Ok(Self(action))
+    }
+}
+
+/// Split the `#[shareable(..)]` attributes from the others, and call `f` with the contents of
+/// each of them.
+fn shareable_attrs(
+    attrs: Vec<Attribute>,
+    mut f: impl FnMut(ParseStream) -> syn::Result<()>,
+) -> syn::Result<Vec<Attribute>> {
+    let mut rest = Vec::new();
+    for attr in attrs {
+        if attr.path().is_ident("shareable") {
+            attr.parse_args_with(|input: ParseStream| {
+                while !input.is_empty() {
+                    f(input)?;
+                    if input.is_empty() {
+                        break;
+                    }
+                    input.parse::<Token![,]>()?;
+                }
+                Ok(())
+            })?;
+        } else {
+            rest.push(attr);
+        }
+    }
+    Ok(rest)
+}
+
+pub fn parse(item: ItemStruct) -> syn::Result<StructDef> {
+    let mut actions = Vec::new();
+    let attrs = shareable_attrs(item.attrs, |input| {
+        let key = input.parse::<Ident>()?;
+        if key != "action" {
+            return Err(syn::Error::new(key.span(), "expected `action(..)`"));
+        }
+        let inner;
+        syn::parenthesized!(inner in input);
+        actions.extend(
+            inner
+                .parse_terminated(ActionAttr::parse, Token![,])?
+                .into_iter()
+                .map(|a| a.0),
+        );
+        Ok(())
+    })?;
+    let Fields::Named(named) = item.fields else {
+        return Err(syn::Error::new_spanned(
+            &item.fields,
+            "shareable structs must have named fields",
+        ));
+    };
+    let mut fields = Vec::new();
+    let mut substructs = Vec::new();
+    for field in named.named {
+        let mut init = None::<Expr>;
+        let mut computed = None::<Expr>;
+        let mut sources = None;
+        let mut substruct = false;
+        let attrs = shareable_attrs(field.attrs, |input| {
+            let key = input.parse::<Ident>()?;
+            if key == "init" {
+                input.parse::<Token![=]>()?;
+                init = Some(input.parse()?);
+            } else if key == "computed" {
+                input.parse::<Token![=]>()?;
+                computed = Some(input.parse()?);
+            } else if key == "from" {
+                input.parse::<Token![=]>()?;
+                sources = Some(parse_sources(input)?);
+            } else if key == "substruct" {
+                substruct = true;
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "expected `init = ..`, `computed = ..`, `from = [..]` or `substruct`",
+                ));
+            }
+            Ok(())
+        })?;
+        let ident = field.ident.expect("named fields have names");
+        match (&init, &computed, &sources) {
+            (Some(init), Some(_), _) => {
+                return Err(syn::Error::new_spanned(
+                    init,
+                    "computed fields are initialized by computing them",
+                ))
+            }
+            (_, Some(c), None) => {
+                return Err(syn::Error::new_spanned(
+                    c,
+                    "computed fields need `from = [..]`, the fields they are computed from",
+                ))
+            }
+            (_, None, Some(_)) => {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "`from = [..]` is only for computed fields (with `computed = ..`)",
+                ))
+            }
+            _ => (),
+        }
+        if substruct {
+            let Type::Path(path) = field.ty else {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    "the type of a substruct must be the path of a shareable struct",
+                ));
+            };
+            if let Some(init) = init.or(computed) {
+                return Err(syn::Error::new_spanned(
+                    init,
+                    "substructs are initialized by their own fields",
+                ));
+            }
+            substructs.push(Substruct {
+                attrs,
+                vis: field.vis,
+                ident,
+                path: path.path,
+            });
+        } else {
+            fields.push(Field {
+                attrs,
+                vis: field.vis,
+                ident,
+                ty: field.ty,
+                init: computed
+                    .or(init)
+                    .unwrap_or_else(|| parse_quote!(Default::default())),
+                sources,
+            });
+        }
+    }
+    Ok(StructDef {
+        krate: krate(),
+        attrs,
+        vis: item.vis,
+        ident: item.ident,
+        generics: item.generics,
+        fields,
+        substructs,
+        actions,
+    })
+}
+
+/// The path of the `dioxus_shareables` crate in the crate using the attribute, which may have
+/// renamed it in its `Cargo.toml`.
+///
+/// (The function-like macros are passed `$crate` instead.)
+fn krate() -> TokenStream {
+    match crate_name("dioxus-shareables") {
+        Ok(FoundCrate::Name(name)) => {
+            let name = Ident::new(&name, Span::call_site());
+            quote!(::#name)
+        }
+        // `Itself` means a doctest, test or example of this crate, none of which can say `crate`.
+        Ok(FoundCrate::Itself) | Err(_) => quote!(::dioxus_shareables),
+    }
+}
+
+/// Reject arguments to the attribute itself.
+pub fn no_args(args: proc_macro2::TokenStream) -> syn::Result<()> {
+    match args.into_iter().next() {
+        None => Ok(()),
+        Some(t) => Err(syn::Error::new_spanned(
+            t,
+            "unexpected argument; actions are declared with `#[shareable(action(..))]`",
+        )),
+    }
+}
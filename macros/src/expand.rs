@@ -0,0 +1,1334 @@
+//! mod `expand` - the code generated by `shareable_struct!`.
+
+use crate::parse::{Action, StructDef};
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    ext::IdentExt, parse_quote, punctuated::Punctuated, GenericParam, Ident, Meta, Path,
+    PathArguments, Token,
+};
+
+/// Convert an identifier to `UpperCamelCase` (the same way as `paste`'s `:camel`.)
+pub fn camel(ident: &Ident) -> String {
+    let mut acc = String::new();
+    let mut prev = '_';
+    for ch in ident.to_string().chars() {
+        if ch != '_' {
+            if prev == '_' {
+                acc.extend(ch.to_uppercase());
+            } else if prev.is_uppercase() {
+                acc.extend(ch.to_lowercase());
+            } else {
+                acc.push(ch);
+            }
+        }
+        prev = ch;
+    }
+    acc
+}
+
+/// Replace the last segment of `path` with `f(last)`, keeping its generic arguments only if
+/// `args` is set.
+pub fn sibling(path: &Path, args: bool, f: impl FnOnce(&Ident) -> Ident) -> Path {
+    let mut path = path.clone();
+    if let Some(last) = path.segments.last_mut() {
+        last.ident = f(&last.ident);
+        if !args {
+            last.arguments = PathArguments::None;
+        }
+    }
+    path
+}
+
+/// Add `arg` to the generic arguments of the last segment of `path`.
+fn with_arg(path: &Path, arg: TokenStream) -> Path {
+    let mut path = path.clone();
+    if let Some(last) = path.segments.last_mut() {
+        match &mut last.arguments {
+            PathArguments::AngleBracketed(a) => a.args.push(parse_quote!(#arg)),
+            args => *args = PathArguments::AngleBracketed(parse_quote!(<#arg>)),
+        }
+    }
+    path
+}
+
+/// `<#(#items),*>`, or nothing if there are no items.
+fn angle<T: ToTokens>(items: &[T]) -> TokenStream {
+    if items.is_empty() {
+        quote!()
+    } else {
+        quote!(<#(#items),*>)
+    }
+}
+
+/// The largest tuple an action is made of (`InductiveMarkerTuple` is only implemented for tuples
+/// up to 52 elements, and the fewer impls the trait solver has to consider, the better.)
+const MAX_TUPLE: usize = 16;
+
+/// The number of fields in each chunk of a record (see `dioxus_shareables::r#struct::Chunk`.)
+const CHUNK: usize = 16;
+
+/// Group `items` into a tuple, nesting them in tuples of at most [`MAX_TUPLE`] elements so that
+/// actions can list any number of items. (Nested tuples of markers are markers themselves.)
+pub fn nest(items: &[TokenStream]) -> TokenStream {
+    if items.len() <= MAX_TUPLE {
+        return quote!((#(#items,)*));
+    }
+    let chunks = items.chunks(items.len().div_ceil(MAX_TUPLE)).map(nest);
+    quote!((#(#chunks,)*))
+}
+
+/// The name under which `__FieldData`/`__ActionData` provide the type of a field or action, as a
+/// `dioxus_shareables::r#struct::Name` list: `kind` followed by the (unaltered) identifier, in
+/// 16-byte segments. Identifiers never contain a space or a NUL byte, so distinct names never get
+/// the same key, however long they are.
+pub fn assoc_key(krate: &TokenStream, kind: &str, ident: &Ident) -> TokenStream {
+    let name = format!("{kind} {}", ident.unraw());
+    name.as_bytes()
+        .chunks(16)
+        .rev()
+        .fold(quote!(()), |next, seg| {
+            let seg = seg
+                .iter()
+                .enumerate()
+                .fold(0u128, |acc, (i, b)| acc | (*b as u128) << (8 * i));
+            let seg = Literal::u128_unsuffixed(seg);
+            quote!(#krate::r#struct::Name<#seg, #next>)
+        })
+}
+
+/// The type of the field or action `ident` of the struct whose actions trait is `actions_tr`.
+pub fn assoc_type(
+    krate: &TokenStream,
+    actions_tr: &impl ToTokens,
+    kind: &str,
+    ident: &Ident,
+) -> TokenStream {
+    let key = assoc_key(krate, kind, ident);
+    let data = format_ident!("__{kind}Data");
+    // Spanned so that an unknown name is reported where it was written.
+    quote_spanned!(ident.span()=> <<() as #actions_tr>::#data as #krate::r#struct::AssocType<#key>>::Type)
+}
+
+/// Check that the struct has no lifetime parameters, that computed fields are computed from plain
+/// fields, and that the actions only refer to fields which exist (and don't write computed fields.)
+fn check(def: &StructDef) -> syn::Result<()> {
+    let mut errors = Vec::new();
+    for p in &def.generics.params {
+        if let GenericParam::Lifetime(l) = p {
+            errors.push(syn::Error::new_spanned(
+                l,
+                "shareable structs can't have lifetime parameters",
+            ));
+        }
+    }
+    let is_field = |i: &Ident| def.fields.iter().any(|f| f.ident == *i);
+    let is_computed = |i: &Ident| {
+        def.fields
+            .iter()
+            .any(|f| f.ident == *i && f.sources.is_some())
+    };
+    let is_substruct = |i: &Ident| def.substructs.iter().any(|g| g.ident == *i);
+    for field in &def.fields {
+        let c = &field.ident;
+        for f in field.sources.iter().flatten() {
+            if is_computed(f) {
+                errors.push(syn::Error::new(
+                    f.span(),
+                    format!("computed field `{c}` can't be computed from computed field `{f}`"),
+                ));
+            } else if !is_field(f) {
+                errors.push(syn::Error::new(
+                    f.span(),
+                    format!("unknown field `{f}` in the sources of computed field `{c}`"),
+                ));
+            }
+        }
+    }
+    let is_action = |i: &Ident| {
+        AUTO_ACTIONS.iter().any(|a| i == a) || def.actions.iter().any(|a| a.ident == *i)
+    };
+    for Action {
+        ident: a,
+        w,
+        rw,
+        r,
+        sub,
+        refs,
+        ..
+    } in &def.actions
+    {
+        if AUTO_ACTIONS.iter().any(|auto| a == auto) {
+            errors.push(syn::Error::new(
+                a.span(),
+                format!("`{a}` is defined automatically for every shareable struct"),
+            ));
+        }
+        for r in refs {
+            if !is_action(r) {
+                errors.push(syn::Error::new(
+                    r.span(),
+                    format!("unknown action `{r}` in action `{a}`"),
+                ));
+            }
+        }
+        for f in w.iter().chain(rw).chain(r) {
+            if is_substruct(f) {
+                errors.push(syn::Error::new(
+                    f.span(),
+                    format!("`{f}` is a substruct in action `{a}`; use `|{f}[ACTION]` instead"),
+                ));
+            } else if !is_field(f) {
+                errors.push(syn::Error::new(
+                    f.span(),
+                    format!("unknown field `{f}` in action `{a}`"),
+                ));
+            }
+        }
+        for f in w.iter().filter(|f| is_computed(f)) {
+            errors.push(syn::Error::new(
+                f.span(),
+                format!("`{f}` is a computed field in action `{a}`; use `R[{f}]` instead"),
+            ));
+        }
+        for f in r.iter().filter(|f| is_field(f) && !is_computed(f)) {
+            errors.push(syn::Error::new(
+                f.span(),
+                format!("`{f}` is not a computed field in action `{a}`; use `RW[{f}]` instead"),
+            ));
+        }
+        for (g, _) in sub {
+            if is_field(g) {
+                errors.push(syn::Error::new(
+                    g.span(),
+                    format!("`{g}` is not a substruct in action `{a}`; use `W[{g}]` or `RW[{g}]` instead"),
+                ));
+            } else if !is_substruct(g) {
+                errors.push(syn::Error::new(
+                    g.span(),
+                    format!("unknown substruct `{g}` in action `{a}`"),
+                ));
+            }
+        }
+    }
+    let mut errors = errors.into_iter();
+    match errors.next() {
+        None => Ok(()),
+        Some(mut e) => {
+            e.extend(errors);
+            Err(e)
+        }
+    }
+}
+
+/// The actions which every struct has: `RW` (or `W`) access to every field, and to every substruct
+/// with its own `ALL_RW` (or `ALL_W`) action. (Computed fields can't be written, even with `W`.)
+const AUTO_ACTIONS: [&str; 2] = ["ALL_RW", "ALL_W"];
+
+/// The actions of the struct, starting with the [`AUTO_ACTIONS`], with the actions each of them
+/// includes merged in. (Expects `def` to have passed `check`.)
+fn resolve(def: &StructDef) -> syn::Result<Vec<Action>> {
+    let mut actions = AUTO_ACTIONS
+        .iter()
+        .map(|a| {
+            let ident = Ident::new(a, def.ident.span());
+            let all = def.fields.iter().map(|f| f.ident.clone()).collect();
+            let sub = def
+                .substructs
+                .iter()
+                .map(|g| (g.ident.clone(), ident.clone()))
+                .collect();
+            let (w, rw) = if *a == "ALL_W" {
+                (all, Vec::new())
+            } else {
+                (Vec::new(), all)
+            };
+            Action {
+                vis: def.vis.clone(),
+                ident,
+                ty: None,
+                tr: None,
+                w,
+                rw,
+                r: Vec::new(),
+                sub,
+                refs: Vec::new(),
+            }
+        })
+        .collect::<Vec<_>>();
+    // Computed fields are read like any other field.
+    actions.extend(def.actions.iter().cloned().map(|mut a| {
+        a.rw.append(&mut a.r);
+        a
+    }));
+
+    // Merge in the included actions depth-first, so that each action is merged into others only
+    // once its own includes have been merged into it.
+    fn visit(
+        i: usize,
+        actions: &mut [Action],
+        visiting: &mut [bool],
+        done: &mut [bool],
+    ) -> syn::Result<()> {
+        if done[i] {
+            return Ok(());
+        }
+        if visiting[i] {
+            let a = &actions[i].ident;
+            return Err(syn::Error::new(
+                a.span(),
+                format!("action `{a}` includes itself"),
+            ));
+        }
+        visiting[i] = true;
+        for r in std::mem::take(&mut actions[i].refs) {
+            let j = actions
+                .iter()
+                .position(|a| a.ident == r)
+                .expect("checked by `check`");
+            visit(j, actions, visiting, done)?;
+            let (w, rw, sub) = (
+                actions[j].w.clone(),
+                actions[j].rw.clone(),
+                actions[j].sub.clone(),
+            );
+            let a = &mut actions[i];
+            a.w.extend(w);
+            a.rw.extend(rw);
+            a.sub.extend(sub);
+        }
+        // Drop repeated items, and write access to fields the action can also read.
+        let a = &mut actions[i];
+        let mut seen = Vec::new();
+        dedup(&mut a.rw, &mut seen);
+        dedup(&mut a.w, &mut seen);
+        dedup(&mut a.sub, &mut Vec::new());
+        done[i] = true;
+        Ok(())
+    }
+    fn dedup<T: Clone + PartialEq>(items: &mut Vec<T>, seen: &mut Vec<T>) {
+        items.retain(|i| {
+            let new = !seen.contains(i);
+            if new {
+                seen.push(i.clone());
+            }
+            new
+        });
+    }
+    let mut visiting = vec![false; actions.len()];
+    let mut done = vec![false; actions.len()];
+    for i in 0..actions.len() {
+        visit(i, &mut actions, &mut visiting, &mut done)?;
+    }
+    Ok(actions)
+}
+
+pub fn expand(def: &StructDef) -> syn::Result<TokenStream> {
+    check(def)?;
+    let actions = resolve(def)?;
+    let StructDef {
+        krate,
+        attrs,
+        vis: v,
+        ident: s,
+        ..
+    } = def;
+    // `#[snapshot(..)]` attributes go on the snapshot struct instead.
+    let (snapshot_attrs, attrs): (Vec<_>, Vec<_>) =
+        attrs.iter().partition(|a| a.path().is_ident("snapshot"));
+    let snapshot_attrs = snapshot_attrs
+        .iter()
+        .map(|a| a.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated))
+        .collect::<syn::Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    let actions_tr = format_ident!("{s}Actions");
+    let as_actions = format_ident!("As{s}Actions");
+    let instance = format_ident!("{s}Instance");
+    let field_data = format_ident!("{s}FieldData");
+    let action_data = format_ident!("{s}ActionData");
+    let share_of = |f: &Ident| format_ident!("{s}FieldShareable{}", camel(f));
+    let marker_of = |g: &Ident| format_ident!("{s}Substruct{}", camel(g));
+
+    // The generic parameters of the struct: as declared (every type parameter must be 'static,
+    // since the fields are shared globally), as in an `impl` (without defaults), and as arguments.
+    let mut decl = Vec::new();
+    let mut imp = Vec::new();
+    let mut args = Vec::new();
+    let mut tp = Vec::new();
+    for p in &def.generics.params {
+        match p {
+            GenericParam::Type(t) => {
+                let mut t = t.clone();
+                t.bounds.push(parse_quote!('static));
+                decl.push(GenericParam::Type(t.clone()));
+                t.eq_token = None;
+                t.default = None;
+                args.push(t.ident.clone());
+                tp.push(t.ident.clone());
+                imp.push(GenericParam::Type(t));
+            }
+            GenericParam::Const(c) => {
+                decl.push(p.clone());
+                let mut c = c.clone();
+                c.eq_token = None;
+                c.default = None;
+                args.push(c.ident.clone());
+                imp.push(GenericParam::Const(c));
+            }
+            GenericParam::Lifetime(_) => unreachable!("rejected by `check`"),
+        }
+    }
+    let generic = !args.is_empty();
+    let preds = def
+        .generics
+        .where_clause
+        .iter()
+        .flat_map(|w| &w.predicates)
+        .collect::<Vec<_>>();
+    let where_with = |extra: TokenStream| {
+        if preds.is_empty() && extra.is_empty() {
+            quote!()
+        } else {
+            quote!(where #(#preds,)* #extra)
+        }
+    };
+    let where_ = where_with(quote!());
+    let decl_angle = angle(&decl);
+    let imp_angle = angle(&imp);
+    let args_angle = angle(&args);
+
+    let f = def.fields.iter().map(|f| &f.ident).collect::<Vec<_>>();
+    let fattrs = def
+        .fields
+        .iter()
+        .map(|f| {
+            let attrs = &f.attrs;
+            quote!(#(#attrs)*)
+        })
+        .collect::<Vec<_>>();
+    let fvis = def.fields.iter().map(|f| &f.vis).collect::<Vec<_>>();
+    let ft = def.fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+    let fflag = f
+        .iter()
+        .map(|f| format_ident!("{}Flag", camel(f)))
+        .collect::<Vec<_>>();
+    let fshare = f.iter().map(|f| share_of(f)).collect::<Vec<_>>();
+    let fstore = f
+        .iter()
+        .map(|f| format_ident!("{s}FieldStorage{}", camel(f)))
+        .collect::<Vec<_>>();
+    let fkey = f
+        .iter()
+        .map(|f| assoc_key(krate, "Field", f))
+        .collect::<Vec<_>>();
+    let where_impl = if generic {
+        where_with(quote!(#(#ft: Send + Sync,)*))
+    } else {
+        where_.clone()
+    };
+    let turbofish = if generic {
+        quote!(::<#(#args),*>)
+    } else {
+        quote!()
+    };
+    // The fields which aren't computed: only these are locked, snapshotted and restored.
+    let plain = def
+        .fields
+        .iter()
+        .filter(|f| f.sources.is_none())
+        .collect::<Vec<_>>();
+    let pf = plain.iter().map(|f| &f.ident).collect::<Vec<_>>();
+    let pfattrs = plain
+        .iter()
+        .map(|f| {
+            let attrs = &f.attrs;
+            quote!(#(#attrs)*)
+        })
+        .collect::<Vec<_>>();
+    let pfvis = plain.iter().map(|f| &f.vis).collect::<Vec<_>>();
+    let pft = plain.iter().map(|f| &f.ty).collect::<Vec<_>>();
+    let pfflag = pf
+        .iter()
+        .map(|f| format_ident!("{}Flag", camel(f)))
+        .collect::<Vec<_>>();
+
+    let g = def.substructs.iter().map(|g| &g.ident).collect::<Vec<_>>();
+    let gattrs = def
+        .substructs
+        .iter()
+        .map(|g| {
+            let attrs = &g.attrs;
+            quote!(#(#attrs)*)
+        })
+        .collect::<Vec<_>>();
+    let gvis = def.substructs.iter().map(|g| &g.vis).collect::<Vec<_>>();
+    let gat = def
+        .substructs
+        .iter()
+        .map(|g| sibling(&g.path, false, |i| format_ident!("{i}Actions")))
+        .collect::<Vec<_>>();
+    let agat = def
+        .substructs
+        .iter()
+        .map(|g| sibling(&g.path, false, |i| format_ident!("As{i}Actions")))
+        .collect::<Vec<_>>();
+    let git = def
+        .substructs
+        .iter()
+        .map(|g| sibling(&g.path, true, |i| format_ident!("{i}Instance")))
+        .collect::<Vec<_>>();
+    let gactions = g
+        .iter()
+        .map(|g| format_ident!("{}Actions", camel(g)))
+        .collect::<Vec<_>>();
+    let gactions_fn = g
+        .iter()
+        .map(|g| format_ident!("{g}_actions"))
+        .collect::<Vec<_>>();
+    let gmarker = g.iter().map(|g| marker_of(g)).collect::<Vec<_>>();
+    // The type of each substruct with the actions given to it by `__Actions`.
+    let gt = def
+        .substructs
+        .iter()
+        .zip(&gactions)
+        .map(|(g, ga)| with_arg(&g.path, quote!(<__Actions as #actions_tr>::#ga)))
+        .collect::<Vec<_>>();
+
+    // The transaction type of each substruct, borrowed for `'__a`.
+    let gtx = def
+        .substructs
+        .iter()
+        .zip(&gactions)
+        .map(|(g, ga)| {
+            let tx = sibling(&g.path, true, |i| format_ident!("{i}Transaction"));
+            let mut tx = with_arg(&tx, quote!(<__Actions as #actions_tr>::#ga));
+            if let Some(PathArguments::AngleBracketed(a)) =
+                tx.segments.last_mut().map(|l| &mut l.arguments)
+            {
+                a.args.insert(0, parse_quote!('__a));
+            }
+            tx
+        })
+        .collect::<Vec<_>>();
+    let gsnap = def
+        .substructs
+        .iter()
+        .map(|g| sibling(&g.path, true, |i| format_ident!("{i}Snapshot")))
+        .collect::<Vec<_>>();
+
+    // Fields of generic structs can't be stored in `static`s, so they are stored in a registry,
+    // keyed by a storage type which has the same generic parameters as the struct. The marker of
+    // the field is kept separate, so that actions don't depend on the generic parameters.
+    // A computed field is initialized by computing it (which also keeps it up to date.)
+    let sinit = def
+        .fields
+        .iter()
+        .map(|field| match field.sources {
+            Some(_) => {
+                let init = format_ident!("__init_{}", field.ident);
+                quote!(#init #turbofish())
+            }
+            None => field.init.to_token_stream(),
+        })
+        .collect::<Vec<_>>();
+    let (storage, store) = if generic {
+        let fstore_where = def
+            .fields
+            .iter()
+            .map(|field| match field.sources {
+                Some(_) => where_impl.clone(),
+                None => {
+                    let ft = &field.ty;
+                    where_with(quote!(#ft: Send + Sync))
+                }
+            })
+            .collect::<Vec<_>>();
+        let phantom = quote!(std::marker::PhantomData<fn() -> (#(#tp,)*)>);
+        let storage = quote! {
+            static __REGISTRY: #krate::shared::Registry = #krate::shared::Registry::new();
+            #(
+                #[derive(Clone, Copy)]
+                #v struct #fshare;
+                #v struct #fstore #imp_angle(#phantom) #where_;
+                impl #imp_angle #krate::shared::Static for #fstore #args_angle
+                #fstore_where
+                {
+                    type Type = #ft;
+                    fn _share(self) -> #krate::Shared<#ft, #krate::W> {
+                        #krate::Shared::from_shareable(
+                            &mut __REGISTRY.shareable::<Self, #ft, _>(|| #sinit),
+                            || unreachable!(),
+                        )
+                    }
+                    fn _use_rw<'a, P>(self, cx: &#krate::reexported::Scope<'a, P>) -> &'a mut #krate::Shared<#ft, #krate::RW> {
+                        #krate::Shared::init(
+                            cx,
+                            &mut __REGISTRY.shareable::<Self, #ft, _>(|| #sinit),
+                            || unreachable!(),
+                            #krate::RW,
+                        )
+                    }
+                    fn _use_w<'a, P>(self, cx: &#krate::reexported::Scope<'a, P>) -> &'a mut #krate::Shared<#ft, #krate::W> {
+                        #krate::Shared::init(
+                            cx,
+                            &mut __REGISTRY.shareable::<Self, #ft, _>(|| #sinit),
+                            || unreachable!(),
+                            #krate::W,
+                        )
+                    }
+                }
+            )*
+        };
+        let store = fstore
+            .iter()
+            .map(|fs| quote!(#fs::#args_angle(std::marker::PhantomData)))
+            .collect::<Vec<_>>();
+        (storage, store)
+    } else {
+        let storage = quote! {
+            #(#krate::shareable!{#v #fshare: #ft = #sinit})*
+        };
+        (storage, fshare.iter().map(|fs| quote!(#fs)).collect())
+    };
+
+    // Each field's accessors (on the struct and on instances), its `FieldOf` impl and how
+    // `new` creates it in an instance. Computed fields can only be read, and also have a struct
+    // of their sources, `__compute_{f}` (which computes them from handles to their sources) and
+    // `__init_{f}` (which computes the global field and keeps it up to date.)
+    let flocal = f
+        .iter()
+        .map(|f| format_ident!("__field_{}", f))
+        .collect::<Vec<_>>();
+    let mut faccess = Vec::new();
+    let mut iaccess = Vec::new();
+    let mut field_of = Vec::new();
+    let mut inew = Vec::new();
+    let mut sources_items = Vec::new();
+    let mut compute_items = Vec::new();
+    let (fvis_all, ft_all, flocal_all) = (&fvis, &ft, &flocal);
+    for (i, field) in def.fields.iter().enumerate() {
+        let (f, fattrs, fvis, ft, fflag, fshare, flocal) = (
+            f[i], &fattrs[i], fvis[i], ft[i], &fflag[i], &fshare[i], &flocal[i],
+        );
+        let Some(sources) = &field.sources else {
+            let init = &field.init;
+            faccess.push(quote! {
+                #fattrs
+                #fvis fn #f(&self) -> &#krate::Shared<#ft, <__Actions as #actions_tr>::#fflag>
+                where <__Actions as #actions_tr>::#fflag: #krate::Flag
+                {
+                    if let Some(#f) = self.#f.as_ref() { #f }
+                    else { unreachable!{} }
+                }
+            });
+            iaccess.push(quote! {
+                #fattrs
+                #fvis fn #f(&self) -> &#krate::Shared<#ft, #krate::W> {
+                    &self.#f
+                }
+            });
+            field_of.push(quote! {
+                impl #krate::r#struct::FieldOf for #fshare {
+                    type WType = InitAs<#fshare, #krate::W>;
+                    type RWType = InitAs<#fshare, #krate::RW>;
+                    type RType = #krate::r#struct::OnlyComputedFieldsAreReadOnly;
+                    const W: Self::WType = InitAs(#fshare, #krate::W);
+                    const RW: Self::RWType = InitAs(#fshare, #krate::RW);
+                    const R: Self::RType = #krate::r#struct::OnlyComputedFieldsAreReadOnly;
+                }
+                impl #krate::r#struct::WriteActions for InitAs<#fshare, #krate::W> {}
+            });
+            // Plain fields are created first, so that computed fields can be computed from them.
+            inew.insert(
+                0,
+                quote! {
+                    let #flocal = #krate::Shared::from_shareable(&mut #krate::shared::Shareable::new(), || #init);
+                },
+            );
+            continue;
+        };
+        faccess.push(quote! {
+            #fattrs
+            #fvis fn #f(&self) -> &#krate::ReadOnly<#ft, <__Actions as #actions_tr>::#fflag>
+            where <__Actions as #actions_tr>::#fflag: #krate::Flag
+            {
+                if let Some(#f) = self.#f.as_ref() { #krate::ReadOnly::new(#f) }
+                else { unreachable!{} }
+            }
+        });
+        iaccess.push(quote! {
+            #fattrs
+            #fvis fn #f(&self) -> &#krate::ReadOnly<#ft, #krate::W> {
+                #krate::ReadOnly::new(&self.#f)
+            }
+        });
+        field_of.push(quote! {
+            impl #krate::r#struct::FieldOf for #fshare {
+                type WType = #krate::r#struct::ComputedFieldsCantBeWritten;
+                type RWType = InitAs<#fshare, #krate::RW>;
+                type RType = InitAs<#fshare, #krate::RW>;
+                const W: Self::WType = #krate::r#struct::ComputedFieldsCantBeWritten;
+                const RW: Self::RWType = InitAs(#fshare, #krate::RW);
+                const R: Self::RType = InitAs(#fshare, #krate::RW);
+            }
+            // Only for `ALL_W`, which can't write it through the `ReadOnly` accessor.
+            impl #krate::r#struct::WriteActions for InitAs<#fshare, #krate::W> {}
+        });
+
+        let source = |src: &Ident| {
+            def.fields
+                .iter()
+                .position(|f| f.ident == *src)
+                .expect("checked by `check`")
+        };
+        let src_vis = sources
+            .iter()
+            .map(|src| fvis_all[source(src)])
+            .collect::<Vec<_>>();
+        let src_ty = sources
+            .iter()
+            .map(|src| ft_all[source(src)])
+            .collect::<Vec<_>>();
+        let src_store = sources
+            .iter()
+            .map(|src| &store[source(src)])
+            .collect::<Vec<_>>();
+        let src_field = sources
+            .iter()
+            .map(|src| &flocal_all[source(src)])
+            .collect::<Vec<_>>();
+        let src_local = sources
+            .iter()
+            .map(|src| format_ident!("__source_{}", src))
+            .collect::<Vec<_>>();
+        let sources_s = format_ident!("{s}{}Sources", camel(f));
+        let sources_doc = format!("The fields which `{f}` of a {s} is computed from.");
+        sources_items.push(quote! {
+            #[doc = #sources_doc]
+            #fvis struct #sources_s<'__a, #(#decl,)*> #where_ {
+                #(#src_vis #sources: &'__a #src_ty,)*
+                #[doc(hidden)]
+                __marker: std::marker::PhantomData<(&'__a (), fn() -> (#(#tp,)*))>,
+            }
+        });
+        let compute = format_ident!("__compute_{}", f);
+        let init = format_ident!("__init_{}", f);
+        let func = &field.init;
+        let store = &store[i];
+        compute_items.push(quote! {
+            fn #compute<#(#imp,)*>(#(#src_local: &#krate::Shared<#src_ty, #krate::W>,)*) -> #ft #where_impl {
+                let mut links = #krate::shared::Links::new();
+                #(links.add(#src_local);)*
+                let locked = links.lock(false);
+                #(let #src_local = locked.read(#src_local);)*
+                let compute: fn(#sources_s<'_, #(#args,)*>) -> #ft = #func;
+                let sources = #sources_s {
+                    #(#sources: &*#src_local,)*
+                    __marker: std::marker::PhantomData,
+                };
+                compute(sources)
+            }
+            fn #init<#(#imp,)*>() -> #ft #where_impl {
+                #krate::shared::watch(
+                    &[#(&#krate::shared::Static::_share(#src_store) as &dyn #krate::shared::Watch),*],
+                    || {
+                        let value = #compute #turbofish(#(&#krate::shared::Static::_share(#src_store)),*);
+                        *#krate::shared::Static::_share(#store).write() = value;
+                    },
+                );
+                #compute #turbofish(#(&#krate::shared::Static::_share(#src_store)),*)
+            }
+        });
+        inew.push(quote! {
+            let #flocal = #krate::Shared::from_shareable(
+                &mut #krate::shared::Shareable::new(),
+                || #compute #turbofish(#(&#src_field),*),
+            );
+            {
+                #(let #src_local = #src_field.downgrade();)*
+                let target = #flocal.downgrade();
+                #krate::shared::watch(&[#(&#src_field as &dyn #krate::shared::Watch),*], move || {
+                    #(let Some(#src_local) = #src_local.upgrade() else { return };)*
+                    let Some(target) = target.upgrade() else { return };
+                    let value = #compute #turbofish(#(&#src_local),*);
+                    *target.write() = value;
+                });
+            }
+        });
+    }
+
+    let write_bounds = quote! {
+        __Actions: #krate::r#struct::WriteActions
+        #(, <__Actions as #actions_tr>::#gactions: #krate::r#struct::WriteActions)*
+    };
+    let instance_doc = format!("An instance of a {s} which is not backed by the global fields.");
+    let snapshot_doc = format!("A plain copy of the fields of a {s}, as taken by `{s}::snapshot`.");
+    let snapshot = format_ident!("{s}Snapshot");
+    let tx_doc = format!("A {s} in a transaction, see `{s}::transaction`.");
+    let tx = format_ident!("{s}Transaction");
+    // The struct with `ALL_W` actions, which snapshots are taken and restored with.
+    let all_w = assoc_type(krate, &actions_tr, "Action", &format_ident!("ALL_W"));
+    let all_w_s = quote!(#s<#(#args,)* #all_w>);
+    let snapshot_where = where_with(quote! {
+        #(#ft: Send + Sync,)*
+        #(for<'__s> #pft: Clone,)*
+        #(for<'__s> #gt: #krate::r#struct::Snapshot<Snapshot = #gsnap>,)*
+    });
+    let new_doc =
+        format!("Create a new instance of {s} with every field set to its initial value.");
+    let actions_doc = format!("Actions on a {s}");
+    let as_actions_doc =
+        format!("Marker trait for allowed conversions between `{actions_tr}` markers.");
+
+    // The record of an action has a chunk parameter for every sixteen fields (holding their
+    // flags) and an actions parameter for each substruct. Each item of an action gives its flag
+    // (or actions) to its own slot and `()` to the rest, and the records of the items are merged
+    // into the record of the action.
+    let chunks = f.len().div_ceil(CHUNK);
+    let rc = (0..chunks)
+        .map(|i| format_ident!("__Chunk{i}"))
+        .collect::<Vec<_>>();
+    let rg = g
+        .iter()
+        .map(|g| format_ident!("__{}Actions", camel(g)))
+        .collect::<Vec<_>>();
+    let rc_b = rc.iter().map(|c| format_ident!("{c}B")).collect::<Vec<_>>();
+    let rg_b = rg.iter().map(|g| format_ident!("{g}B")).collect::<Vec<_>>();
+    let cidx = (0..chunks).map(syn::Index::from).collect::<Vec<_>>();
+    let gidx = (chunks..chunks + g.len())
+        .map(syn::Index::from)
+        .collect::<Vec<_>>();
+    let fchunk = (0..f.len()).map(|i| &rc[i / CHUNK]).collect::<Vec<_>>();
+    let fslot = (0..f.len())
+        .map(|i| format_ident!("F{}", i % CHUNK))
+        .collect::<Vec<_>>();
+    let units = |n: usize| vec![quote!(()); n];
+    let empty = {
+        let u = units(chunks + g.len());
+        quote!(__Record<#(#u,)*>)
+    };
+    let mut item_records = Vec::new();
+    for (i, fi) in fshare.iter().enumerate() {
+        let mut flags = units(CHUNK);
+        flags[i % CHUNK] = quote!(A);
+        let mut types = units(chunks + g.len());
+        types[i / CHUNK] = quote!(#krate::r#struct::Chunk<#(#flags),*>);
+        let mut values = units(chunks + g.len());
+        values[i / CHUNK] = quote!(#krate::r#struct::Chunk::new());
+        item_records.push(quote! {
+            impl<A: #krate::InitType> #krate::r#struct::CollectInto<#empty> for InitAs<#fi, A> {
+                type Record = __Record<#(#types,)*>;
+                fn record(&self) -> Self::Record {
+                    __Record(#(#values,)*)
+                }
+            }
+        });
+    }
+    for (i, (gi, giat)) in gmarker.iter().zip(&gat).enumerate() {
+        let mut types = units(chunks + g.len());
+        types[chunks + i] = quote!(A);
+        let mut values = units(chunks + g.len());
+        values[chunks + i] = quote!(self.1);
+        item_records.push(quote! {
+            impl<A: #giat> #krate::r#struct::CollectInto<#empty> for InitAs<#gi, A> {
+                type Record = __Record<#(#types,)*>;
+                fn record(&self) -> Self::Record {
+                    __Record(#(#values,)*)
+                }
+            }
+        });
+    }
+    let empty_values = units(chunks + g.len());
+
+    let sub_actions_tr = |g: &Ident| {
+        let i = def
+            .substructs
+            .iter()
+            .position(|s| s.ident == *g)
+            .expect("checked by `check`");
+        &gat[i]
+    };
+    let mut action_items = Vec::new();
+    let mut action_consts = Vec::new();
+    let mut action_assoc = Vec::new();
+    let mut auto_consts = Vec::new();
+    for a in &actions {
+        let ident = &a.ident;
+        let av = &a.vis;
+        let w = a.w.iter().map(&share_of).collect::<Vec<_>>();
+        let rw = a.rw.iter().map(&share_of).collect::<Vec<_>>();
+        let sa = a.sub.iter().map(|(g, _)| marker_of(g)).collect::<Vec<_>>();
+        let sa_tr = a
+            .sub
+            .iter()
+            .map(|(g, _)| sub_actions_tr(g))
+            .collect::<Vec<_>>();
+        let sa_action = a.sub.iter().map(|(_, a)| a).collect::<Vec<_>>();
+        let ty = nest(
+            &w.iter()
+                .map(|w| quote!(InitAs<#w, #krate::W>))
+                .chain(rw.iter().map(|rw| quote!(InitAs<#rw, #krate::RW>)))
+                .chain(
+                    sa.iter()
+                        .zip(&sa_tr)
+                        .zip(&sa_action)
+                        .map(|((sa, sa_tr), sa_action)| {
+                            let sa_ty = assoc_type(krate, sa_tr, "Action", sa_action);
+                            quote!(InitAs<#sa, #sa_ty>)
+                        }),
+                )
+                .collect::<Vec<_>>(),
+        );
+        let value = nest(
+            &w.iter()
+                .map(|w| quote!(InitAs(#w, #krate::W)))
+                .chain(rw.iter().map(|rw| quote!(InitAs(#rw, #krate::RW))))
+                .chain(sa.iter().zip(&sa_tr).zip(&sa_action).map(|((sa, sa_tr), sa_action)| {
+                    quote!(InitAs(#sa, <() as #sa_tr>::__ActionData::#sa_action))
+                }))
+                .collect::<Vec<_>>(),
+        );
+        action_consts.push(quote! {
+            pub const #ident: #ty = #value;
+        });
+        let key = assoc_key(krate, "Action", ident);
+        action_assoc.push(quote! {
+            impl #krate::r#struct::AssocType<#key> for #action_data { type Type = #ty; }
+        });
+        let assoc = assoc_type(krate, &actions_tr, "Action", ident);
+        let value = quote!(<() as #actions_tr>::__ActionData::#ident);
+        if AUTO_ACTIONS.iter().any(|auto| ident == auto) {
+            // These are associated constants, so that several structs can share a module.
+            let doc = if ident == "ALL_RW" {
+                "Read-write access to every field, and to every substruct with its `ALL_RW` action."
+            } else {
+                "Write access to every field (computed fields can only be read), and to every substruct with its `ALL_W` action."
+            };
+            auto_consts.push(quote! {
+                #[doc = #doc]
+                #av const #ident: #assoc = #value;
+            });
+            continue;
+        }
+        action_items.push(quote! {
+            #av const #ident: #assoc = #value;
+        });
+        if let Some((tv, ty)) = &a.ty {
+            action_items.push(quote!(#tv type #ty = #assoc;));
+        }
+        if let Some((trv, tr)) = &a.tr {
+            action_items.push(quote! {
+                #trv trait #tr: #as_actions<#assoc> {}
+                impl<T: #as_actions<#assoc>> #tr for T {}
+            });
+        }
+    }
+
+    Ok(quote! {
+        #(#attrs)*
+        #v struct #s<#(#decl,)* __Actions: #actions_tr = ()> #where_ {
+            #(#f: Option<#krate::Shared<#ft, <__Actions as #actions_tr>::#fflag>>,)*
+            #(#g: #gt,)*
+            #[doc(hidden)]
+            __actions_marker: std::marker::PhantomData<(__Actions, fn() -> (#(#tp,)*))>,
+        }
+        #[doc = #instance_doc]
+        ///
+        /// Each field of the instance is stored separately, so that components which use the
+        /// instance with different actions are updated independently. Cloning an instance gives
+        /// another handle to the same fields.
+        #v struct #instance #decl_angle #where_ {
+            #(#f: #krate::Shared<#ft, #krate::W>,)*
+            #(#g: #git,)*
+            #[doc(hidden)]
+            __generics_marker: std::marker::PhantomData<fn() -> (#(#tp,)*)>,
+        }
+        #[doc = #snapshot_doc]
+        #(#[#snapshot_attrs])*
+        #v struct #snapshot #decl_angle #where_ {
+            #(#pfattrs #pfvis #pf: #pft,)*
+            #(#gattrs #gvis #g: #gsnap,)*
+        }
+        #[doc = #tx_doc]
+        #v struct #tx<'__a, #(#decl,)* __Actions: #actions_tr = ()> #where_ {
+            state: &'__a #s<#(#args,)* __Actions>,
+            locked: &'__a #krate::shared::Locked,
+        }
+        #(#sources_items)*
+        impl #imp_angle Clone for #instance #args_angle #where_ {
+            fn clone(&self) -> Self {
+                Self {
+                    #(#f: self.#f.clone(),)*
+                    #(#g: self.#g.clone(),)*
+                    __generics_marker: std::marker::PhantomData,
+                }
+            }
+        }
+        impl #imp_angle PartialEq for #instance #args_angle #where_ {
+            /// Instances are equal when they are handles to the same fields.
+            fn eq(&self, o: &Self) -> bool {
+                true #(&& self.#f.ptr_eq(&o.#f))* #(&& self.#g == o.#g)*
+            }
+        }
+        #[doc = #actions_doc]
+        #[doc = "See [`dioxus_shareables::shareable_struct`] for more info"]
+        /// An actions object describes a collection of field access types you might use
+        /// together.
+        #v trait #actions_tr: 'static + Copy {
+            #(type #fflag: #krate::InitType;)*
+            #(
+                type #gactions: #gat;
+                fn #gactions_fn(&self) -> Self::#gactions;
+            )*
+            #[doc(hidden)]
+            type __FieldData;
+            #[doc(hidden)]
+            type __ActionData;
+        }
+        #[doc = #as_actions_doc]
+        #[doc = "Implementing this yourself can lead to undefined behavior."]
+        #v trait #as_actions<B: #actions_tr>: #actions_tr {}
+        #(#action_items)*
+        #[allow(dead_code)]
+        const _: () = {
+            #v struct #field_data;
+            #v struct #action_data;
+            #[derive(Clone, Copy)]
+            #v struct InitAs<F, A>(F, A);
+
+            impl<#(#imp,)* __Actions: #actions_tr> #krate::r#struct::ShareableStruct for #s<#(#args,)* __Actions> #where_ {
+                type Fields = #field_data;
+                type Actions = #action_data;
+            }
+
+            #storage
+            #(#compute_items)*
+        impl #imp_angle #instance #args_angle #where_impl {
+                #[doc = #new_doc]
+                #v fn new() -> Self {
+                    #(#inew)*
+                    Self {
+                        #(#f: #flocal,)*
+                        #(#g: <#git>::new(),)*
+                        __generics_marker: std::marker::PhantomData,
+                    }
+                }
+                #(#iaccess)*
+                #(
+                    #gattrs
+                    #gvis fn #g(&self) -> &#git {
+                        &self.#g
+                    }
+                )*
+            }
+            impl #imp_angle Default for #instance #args_angle #where_impl {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+            #(#field_of)*
+            #(
+                #[derive(Clone, Copy)]
+                #v struct #gmarker;
+                impl<A: #krate::r#struct::WriteActions> #krate::r#struct::WriteActions for InitAs<#gmarker, A> {}
+            )*
+
+            impl<#(#imp,)* __Actions: #actions_tr> #s<#(#args,)* __Actions> #where_impl {
+                #v fn share(__a: __Actions) -> Self where #write_bounds {
+                    #[allow(unused_mut)]
+                    let mut self_ = Self {
+                        #(#f: None,)*
+                        #(#g: <#gt>::share(#actions_tr::#gactions_fn(&__a)),)*
+                        __actions_marker: std::marker::PhantomData,
+                    };
+                    #(
+                        <__Actions::#fflag as #krate::InitType>::share_field(&mut self_.#f, #store);
+                    )*
+                    self_
+                }
+                #[doc(hidden)]
+                #v fn __uninit() -> Self {
+                    Self {
+                        #(#f: None,)*
+                        #(#g: <#gt>::__uninit(),)*
+                        __actions_marker: std::marker::PhantomData,
+                    }
+                }
+                #[doc(hidden)]
+                #v fn __init_in<P>(&mut self, cx: &#krate::reexported::Scope<P>) {
+                    #(
+                        <__Actions::#fflag as #krate::InitType>::init_field(cx, &mut self.#f, #store);
+                    )*
+                    #(self.#g.__init_in(cx);)*
+                }
+                #v fn use_<'a, P>(cx: &#krate::reexported::Scope<'a, P>, _: __Actions) -> &'a mut Self {
+                    cx.use_hook(|| {
+                        let mut self_ = Self::__uninit();
+                        self_.__init_in(cx);
+                        self_
+                    })
+                }
+                /// Use an instance stored in a `List` entry.
+                ///
+                /// Like `use_`, but the fields are those of the instance rather than of the global.
+                #v fn use_entry<'a, P>(
+                    cx: &#krate::reexported::Scope<'a, P>,
+                    entry: &#krate::ListEntry<#instance #args_angle>,
+                    _: __Actions,
+                ) -> &'a mut Self {
+                    cx.use_hook(|| {
+                        let mut self_ = Self::__uninit();
+                        self_.__init_from(cx, &entry.share().read());
+                        self_
+                    })
+                }
+                /// Use a separate instance of the struct (see `new_instance`).
+                ///
+                /// Like `use_`, but the fields are those of `instance` rather than of the global.
+                #v fn use_in<'a, P>(
+                    cx: &#krate::reexported::Scope<'a, P>,
+                    instance: &#instance #args_angle,
+                    _: __Actions,
+                ) -> &'a mut Self {
+                    cx.use_hook(|| {
+                        let mut self_ = Self::__uninit();
+                        self_.__init_from(cx, instance);
+                        self_
+                    })
+                }
+                /// Get a separate instance of the struct without registering a hook.
+                #v fn share_in(instance: &#instance #args_angle, __a: __Actions) -> Self where #write_bounds {
+                    Self::__share_from(__a, instance)
+                }
+                /// Get an instance stored in a `List` entry without registering a hook.
+                #v fn share_entry(entry: &#krate::ListEntry<#instance #args_angle>, __a: __Actions) -> Self where #write_bounds {
+                    Self::__share_from(__a, &entry.share().read())
+                }
+                #[doc(hidden)]
+                #v fn __share_from(__a: __Actions, instance: &#instance #args_angle) -> Self where #write_bounds {
+                    #[allow(unused_mut)]
+                    let mut self_ = Self {
+                        #(#f: None,)*
+                        #(#g: <#gt>::__share_from(#actions_tr::#gactions_fn(&__a), &instance.#g),)*
+                        __actions_marker: std::marker::PhantomData,
+                    };
+                    #(
+                        <__Actions::#fflag as #krate::InitType>::share_field_from(
+                            &mut self_.#f,
+                            &instance.#f,
+                        );
+                    )*
+                    self_
+                }
+                #[doc(hidden)]
+                #v fn __init_from<P>(&mut self, cx: &#krate::reexported::Scope<P>, instance: &#instance #args_angle) {
+                    #(
+                        <__Actions::#fflag as #krate::InitType>::init_field_from(
+                            cx,
+                            &mut self.#f,
+                            &instance.#f,
+                        );
+                    )*
+                    #(self.#g.__init_from(cx, &instance.#g);)*
+                }
+                /// Read and write several fields at once.
+                ///
+                /// Every field which the actions give access to (including those of substructs) is
+                /// locked, and `f` is called with a transaction through which they can be read and
+                /// written. Once `f` returns, the fields are unlocked, and each component which
+                /// holds a RW link to any field which was written is marked as needing update once.
+                ///
+                /// The fields must not be accessed other than through the transaction until it
+                /// ends, or the thread will deadlock.
+                #v fn transaction<R>(&self, f: impl FnOnce(&#tx<'_, #(#args,)* __Actions>) -> R) -> R {
+                    let mut links = #krate::shared::Links::new();
+                    self.__links(&mut links);
+                    let locked = links.lock(true);
+                    f(&#tx::__new(self, &locked))
+                }
+                #[doc(hidden)]
+                #v fn __links(&self, links: &mut #krate::shared::Links) {
+                    #(if let Some(#pf) = self.#pf.as_ref() { links.add(#pf); })*
+                    #(self.#g.__links(links);)*
+                }
+                #[doc(hidden)]
+                #v fn __snapshot(&self) -> <Self as #krate::r#struct::Snapshot>::Snapshot
+                where Self: #krate::r#struct::Snapshot
+                {
+                    let mut links = #krate::shared::Links::new();
+                    self.__links(&mut links);
+                    #krate::r#struct::Snapshot::snapshot_locked(self, &links.lock(false))
+                }
+                #[doc(hidden)]
+                #v fn __restore_locked(&self, locked: &#krate::shared::Locked, snapshot: #snapshot #args_angle) {
+                    #(if let Some(#pf) = self.#pf.as_ref() { *locked.write(#pf) = snapshot.#pf; })*
+                    #(self.#g.__restore_locked(locked, snapshot.#g);)*
+                }
+                #[doc(hidden)]
+                #v fn __restore(&self, snapshot: #snapshot #args_angle) {
+                    let mut links = #krate::shared::Links::new();
+                    self.__links(&mut links);
+                    self.__restore_locked(&links.lock(true), snapshot);
+                }
+                #v fn with_actions<B: #actions_tr>(&self, _: B) -> &#s<#(#args,)* B>
+                where __Actions: #as_actions<B>
+                {
+                    // SAFETY:
+                    //   * the layout of the struct does not depend on its actions.
+                    //   * the `As..Actions` trait guarantees that an initialized `Self` has
+                    //     initialized all the fields that should be initialized with actions `B`.
+                    unsafe { std::mem::transmute(self) }
+                }
+                #(#faccess)*
+                #(
+                    #gattrs
+                    #gvis fn #g(&self) -> &#gt {
+                        &self.#g
+                    }
+                )*
+            }
+            impl #imp_angle #s<#(#args,)*> #where_ {
+                #(#auto_consts)*
+            }
+            impl #imp_angle #s<#(#args,)*> #where_impl {
+                /// Create a new instance of the struct, with every field set to its initial value.
+                ///
+                /// The instance is independent of the global fields, and can be used with
+                /// `use_in` (or `share_in`) with any actions.
+                #v fn new_instance() -> #instance #args_angle {
+                    <#instance #args_angle>::new()
+                }
+                /// Read every field of the struct (and of its substructs) into a snapshot.
+                ///
+                /// The fields are all locked while they are read, so the snapshot never mixes
+                /// values from before and after a `restore` (or a transaction.)
+                #v fn snapshot() -> #snapshot #args_angle
+                where for<'__s> #all_w_s: #krate::r#struct::Snapshot<Snapshot = #snapshot #args_angle>
+                {
+                    <#all_w_s>::share(Self::ALL_W).__snapshot()
+                }
+                /// Write every field of a snapshot back to the struct (and to its substructs.)
+                ///
+                /// The fields are all locked while they are written, and each component which
+                /// uses any of them is then marked as needing update once.
+                #v fn restore(snapshot: #snapshot #args_angle) {
+                    <#all_w_s>::share(Self::ALL_W).__restore(snapshot)
+                }
+            }
+            impl #imp_angle #instance #args_angle #where_impl {
+                /// Read every field of the instance into a snapshot (see `snapshot` on the struct.)
+                #v fn snapshot(&self) -> #snapshot #args_angle
+                where for<'__s> #all_w_s: #krate::r#struct::Snapshot<Snapshot = #snapshot #args_angle>
+                {
+                    <#all_w_s>::share_in(self, <#s #args_angle>::ALL_W).__snapshot()
+                }
+                /// Write every field of a snapshot back to the instance (see `restore` on the
+                /// struct.)
+                #v fn restore(&self, snapshot: #snapshot #args_angle) {
+                    <#all_w_s>::share_in(self, <#s #args_angle>::ALL_W).__restore(snapshot)
+                }
+            }
+            impl<'__a, #(#imp,)* __Actions: #actions_tr> #tx<'__a, #(#args,)* __Actions> #where_impl {
+                #[doc(hidden)]
+                #v fn __new(state: &'__a #s<#(#args,)* __Actions>, locked: &'__a #krate::shared::Locked) -> Self {
+                    Self { state, locked }
+                }
+                #(
+                    #pfattrs
+                    #pfvis fn #pf(&self) -> #krate::shared::LockedField<'__a, #pft>
+                    where <__Actions as #actions_tr>::#pfflag: #krate::Flag
+                    {
+                        #krate::shared::LockedField::new(self.locked, self.state.#pf())
+                    }
+                )*
+                #(
+                    #gattrs
+                    #gvis fn #g(&self) -> #gtx {
+                        <#gtx>::__new(self.state.#g(), self.locked)
+                    }
+                )*
+            }
+            impl<#(#imp,)* __Actions: #actions_tr> #krate::r#struct::Snapshot for #s<#(#args,)* __Actions>
+            #snapshot_where
+            {
+                type Snapshot = #snapshot #args_angle;
+                fn snapshot_locked(&self, locked: &#krate::shared::Locked) -> Self::Snapshot {
+                    #snapshot {
+                        #(#pf: <#pft as Clone>::clone(&locked.read(self.#pf.as_ref().expect("snapshots are taken with `ALL_W`"))),)*
+                        #(#g: #krate::r#struct::Snapshot::snapshot_locked(&self.#g, locked),)*
+                    }
+                }
+            }
+            impl<#(#imp,)* A: #actions_tr, B: #as_actions<A>> AsRef<#s<#(#args,)* A>> for #s<#(#args,)* B> #where_ {
+                fn as_ref(&self) -> &#s<#(#args,)* A> {
+                    // SAFETY: see `with_actions`.
+                    unsafe { std::mem::transmute(self) }
+                }
+            }
+            impl<#(#imp,)* A: #actions_tr, B: #as_actions<A>> AsMut<#s<#(#args,)* A>> for #s<#(#args,)* B> #where_ {
+                fn as_mut(&mut self) -> &mut #s<#(#args,)* A> {
+                    // SAFETY: see `with_actions`.
+                    unsafe { std::mem::transmute(self) }
+                }
+            }
+            impl<A: #actions_tr, B: #actions_tr> #as_actions<B> for A
+            where
+                #(<A as #actions_tr>::#fflag: #krate::r#struct::ImpliesInitField<<B as #actions_tr>::#fflag>,)*
+                #(<A as #actions_tr>::#gactions: #agat<<B as #actions_tr>::#gactions>,)*
+            {
+            }
+
+            impl #action_data {
+                #(#action_consts)*
+            }
+            // Looked up by `struct_actions!`, so that an unknown field is reported by name.
+            #[allow(non_upper_case_globals)]
+            impl #field_data {
+                #(pub const #f: () = ();)*
+            }
+            #[derive(Clone, Copy)]
+            #v struct __Record<#(#rc,)* #(#rg,)*>(#(#rc,)* #(#rg,)*);
+            impl #krate::r#struct::EmptyRecord for #empty {
+                const EMPTY: Self = __Record(#(#empty_values,)*);
+            }
+            impl<#(#rc: Copy,)* #(#rg: Copy,)* #(#rc_b: Copy,)* #(#rg_b: Copy,)*>
+                #krate::r#struct::Merge<__Record<#(#rc_b,)* #(#rg_b,)*>> for __Record<#(#rc,)* #(#rg,)*>
+            where
+                #(#rc: #krate::r#struct::Merge<#rc_b>,)*
+            {
+                type Output = __Record<
+                    #(<#rc as #krate::r#struct::Merge<#rc_b>>::Output,)*
+                    #((#rg, #rg_b),)*
+                >;
+                fn merge(self, o: __Record<#(#rc_b,)* #(#rg_b,)*>) -> Self::Output {
+                    __Record(
+                        #(#krate::r#struct::Merge::merge(self.#cidx, o.#cidx),)*
+                        #((self.#gidx, o.#gidx),)*
+                    )
+                }
+            }
+            #(#item_records)*
+            impl<
+                A: 'static + Copy + #krate::r#struct::CollectInto<#empty, Record = __Record<#(#rc,)* #(#rg,)*>>,
+                #(#rc: #krate::r#struct::ChunkFlags,)*
+                #(#rg: #gat,)*
+            > #actions_tr for A
+            {
+                #(type #fflag = <#fchunk as #krate::r#struct::ChunkFlags>::#fslot;)*
+                #(
+                    type #gactions = #rg;
+                    fn #gactions_fn(&self) -> #rg {
+                        #krate::r#struct::CollectInto::record(self).#gidx
+                    }
+                )*
+                type __FieldData = #field_data;
+                type __ActionData = #action_data;
+            }
+
+            #(impl #krate::r#struct::AssocType<#fkey> for #field_data { type Type = #fshare; })*
+            #(#action_assoc)*
+        };
+    })
+}
@@ -0,0 +1,55 @@
+//! Crate [`dioxus-shareables-macros`](crate)
+//!
+//! Procedural macros for [`dioxus-shareables`](https://docs.rs/dioxus-shareables). These are
+//! used through the macros of that crate, and are not meant to be used directly.
+
+mod actions;
+mod attr;
+mod expand;
+mod parse;
+
+/// The implementation of `dioxus_shareables::shareable_struct!`.
+///
+/// The input is the path of the `dioxus_shareables` crate (i.e. `$crate`) followed by the input
+/// of `shareable_struct!`.
+#[proc_macro]
+pub fn shareable_struct(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let def = syn::parse_macro_input!(input as parse::StructDef);
+    expand::expand(&def)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The implementation of `dioxus_shareables::struct_actions!`.
+///
+/// The input is the path of the `dioxus_shareables` crate followed by the input of
+/// `struct_actions!`.
+#[proc_macro]
+pub fn struct_actions(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let def = syn::parse_macro_input!(input as actions::StructActions);
+    actions::expand(&def).into()
+}
+
+/// The implementation of `dioxus_shareables::struct_assoc_type!`.
+///
+/// The input is the path of the `dioxus_shareables` crate followed by the input of
+/// `struct_assoc_type!`.
+#[proc_macro]
+pub fn struct_assoc_type(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let def = syn::parse_macro_input!(input as actions::AssocLookup);
+    actions::expand_lookup(&def).into()
+}
+
+/// The implementation of `dioxus_shareables::attr::shareable_struct`.
+#[proc_macro_attribute]
+pub fn shareable_struct_attr(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let item = syn::parse_macro_input!(item as syn::ItemStruct);
+    attr::no_args(args.into())
+        .and_then(|()| attr::parse(item))
+        .and_then(|def| expand::expand(&def))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
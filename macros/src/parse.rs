@@ -0,0 +1,228 @@
+//! mod `parse` - the syntax of `shareable_struct!`.
+
+use proc_macro2::TokenStream;
+use syn::{
+    braced, bracketed, parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Expr, Generics, Ident, Path, Token, Type, Visibility,
+};
+
+/// A whole `shareable_struct!` invocation, preceded by the path of the `dioxus_shareables` crate.
+pub struct StructDef {
+    pub krate: TokenStream,
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub ident: Ident,
+    pub generics: Generics,
+    pub fields: Vec<Field>,
+    pub substructs: Vec<Substruct>,
+    pub actions: Vec<Action>,
+}
+
+/// `$vis $ident: $ty = $init` or `$vis $ident: $ty = computed($init) from [$($sources),*]`
+pub struct Field {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub ident: Ident,
+    pub ty: Type,
+    /// The initial value of the field, or the function which computes a computed field.
+    pub init: Expr,
+    /// The fields a computed field is computed from.
+    pub sources: Option<Vec<Ident>>,
+}
+
+/// `|$vis $ident: $path` (the path may have generic arguments.)
+pub struct Substruct {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub ident: Ident,
+    pub path: Path,
+}
+
+/// `$vis action $ident$(: $vis $type)?$(impl $vis $trait)? = $items;`
+#[derive(Clone)]
+pub struct Action {
+    pub vis: Visibility,
+    pub ident: Ident,
+    pub ty: Option<(Visibility, Ident)>,
+    pub tr: Option<(Visibility, Ident)>,
+    pub w: Vec<Ident>,
+    pub rw: Vec<Ident>,
+    /// Computed fields, which are read like `rw` fields but can't be written.
+    pub r: Vec<Ident>,
+    pub sub: Vec<(Ident, Ident)>,
+    /// Other actions of the same struct which this action includes.
+    pub refs: Vec<Ident>,
+}
+
+impl Parse for StructDef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse::<proc_macro2::TokenTree>()?.into();
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![struct]>()?;
+        let ident = input.parse()?;
+        let mut generics = input.parse::<Generics>()?;
+        generics.where_clause = input.parse()?;
+        let body;
+        braced!(body in input);
+        let mut fields = Vec::new();
+        let mut substructs = Vec::new();
+        while !body.is_empty() {
+            let attrs = body.call(Attribute::parse_outer)?;
+            if body.peek(Token![|]) {
+                body.parse::<Token![|]>()?;
+                let vis = body.parse()?;
+                let ident = body.parse()?;
+                body.parse::<Token![:]>()?;
+                let path = body.parse::<Path>()?;
+                substructs.push(Substruct {
+                    attrs,
+                    vis,
+                    ident,
+                    path,
+                });
+            } else {
+                let vis = body.parse()?;
+                let ident = body.parse()?;
+                body.parse::<Token![:]>()?;
+                let ty = body.parse()?;
+                body.parse::<Token![=]>()?;
+                let (init, sources) = parse_init(&body)?;
+                fields.push(Field {
+                    attrs,
+                    vis,
+                    ident,
+                    ty,
+                    init,
+                    sources,
+                });
+            }
+            if body.is_empty() {
+                break;
+            }
+            body.parse::<Token![,]>()?;
+        }
+        let mut actions = Vec::new();
+        while !input.is_empty() {
+            actions.push(input.parse()?);
+        }
+        Ok(Self {
+            krate,
+            attrs,
+            vis,
+            ident,
+            generics,
+            fields,
+            substructs,
+            actions,
+        })
+    }
+}
+
+/// Parse `computed($init) from [$($sources),*]`, or any other expression as a plain initial value.
+fn parse_init(input: ParseStream) -> syn::Result<(Expr, Option<Vec<Ident>>)> {
+    let is_computed = {
+        let fork = input.fork();
+        fork.parse::<Ident>().is_ok_and(|i| i == "computed")
+            && fork.peek(syn::token::Paren)
+            && fork.parse::<proc_macro2::TokenTree>().is_ok()
+            && fork.parse::<Ident>().is_ok_and(|i| i == "from")
+    };
+    if !is_computed {
+        return Ok((input.parse()?, None));
+    }
+    input.parse::<Ident>()?;
+    let init;
+    parenthesized!(init in input);
+    let init = init.parse()?;
+    input.parse::<Ident>()?;
+    Ok((init, Some(parse_sources(input)?)))
+}
+
+/// `[$($sources),*]`
+pub fn parse_sources(input: ParseStream) -> syn::Result<Vec<Ident>> {
+    let inner;
+    bracketed!(inner in input);
+    Ok(Punctuated::<Ident, Token![,]>::parse_terminated(&inner)?
+        .into_iter()
+        .collect())
+}
+
+impl Parse for Action {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        let action = input.parse::<Ident>()?;
+        if action != "action" {
+            return Err(syn::Error::new(action.span(), "expected `action`"));
+        }
+        let ident = input.parse()?;
+        let mut ty = None;
+        if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            ty = Some((input.parse()?, input.parse()?));
+        }
+        let mut tr = None;
+        if input.peek(Token![impl]) {
+            input.parse::<Token![impl]>()?;
+            tr = Some((input.parse()?, input.parse()?));
+        }
+        input.parse::<Token![=]>()?;
+        let mut action = Self {
+            vis,
+            ident,
+            ty,
+            tr,
+            w: Vec::new(),
+            rw: Vec::new(),
+            r: Vec::new(),
+            sub: Vec::new(),
+            refs: Vec::new(),
+        };
+        while !input.peek(Token![;]) {
+            action.parse_item(input)?;
+        }
+        input.parse::<Token![;]>()?;
+        Ok(action)
+    }
+}
+
+impl Action {
+    /// Parse one of `W[..]`, `RW[..]`, `R[..]`, `|field[ACTION]` or `ACTION`, optionally preceded
+    /// by a `+`.
+    pub fn parse_item(&mut self, input: ParseStream) -> syn::Result<()> {
+        if input.peek(Token![+]) {
+            input.parse::<Token![+]>()?;
+        }
+        if input.peek(Token![|]) {
+            input.parse::<Token![|]>()?;
+            let field = input.parse()?;
+            let inner;
+            bracketed!(inner in input);
+            self.sub.push((field, inner.parse()?));
+            return Ok(());
+        }
+        let expected = "expected `W[..]`, `RW[..]`, `R[..]`, `|field[ACTION]` or `ACTION`";
+        let flag = input
+            .parse::<Ident>()
+            .map_err(|e| syn::Error::new(e.span(), expected))?;
+        if !input.peek(syn::token::Bracket) {
+            self.refs.push(flag);
+            return Ok(());
+        }
+        let list = if flag == "W" {
+            &mut self.w
+        } else if flag == "RW" {
+            &mut self.rw
+        } else if flag == "R" {
+            &mut self.r
+        } else {
+            return Err(syn::Error::new(flag.span(), expected));
+        };
+        let inner;
+        bracketed!(inner in input);
+        list.extend(Punctuated::<Ident, Token![,]>::parse_terminated(&inner)?);
+        Ok(())
+    }
+}
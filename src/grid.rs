@@ -0,0 +1,299 @@
+//! mod `grid` - two dimensional tables of shared values.
+//!
+//! See [`Grid`] for more info.
+
+use crate::list::{EntryWatchers, ListEntry};
+use crate::shared::{Link, ReadOnly, Shareable, Shared};
+use std::sync::{Arc, Weak};
+
+/// A table of shareable values, arranged in rows and columns.
+///
+/// Each cell of the grid is a [`ListEntry`], so a component which is passed a cell is updated only
+/// when that cell changes. Cells keep their identity when rows or columns are inserted or removed
+/// around them.
+///
+/// Components which display a whole row or column (e.g. to show its total) can subscribe to it
+/// with [`GridLine::use_rw`]; they are updated when any cell in the line changes, or when cells
+/// are added to or removed from it.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// use dioxus_shareables::{shareable, Grid, GridLine, ListEntry};
+///
+/// shareable!(Sheet: Grid<i64> = Grid::from_fn(3, 2, |r, c| (r * 2 + c) as i64));
+///
+/// #[allow(non_snake_case)]
+/// #[inline_props]
+/// fn Cell(cx: Scope, cell: ListEntry<i64>) -> Element {
+///     let cell = cell.use_rw(&cx); // This component is updated when this cell changes.
+///     cx.render(rsx! { td { "{cell.read()}" } })
+/// }
+///
+/// #[allow(non_snake_case)]
+/// #[inline_props]
+/// fn ColumnTotal(cx: Scope, column: GridLine<i64>) -> Element {
+///     let column = column.use_rw(&cx); // This component is updated when any cell in the column
+///                                      // changes.
+///     let total: i64 = column.read().iter().map(|c| *c.share().read()).sum();
+///     cx.render(rsx! { td { "{total}" } })
+/// }
+///
+/// let sheet = Sheet.share();
+/// assert_eq!(*sheet.read().cell(2, 1).share().read(), 5);
+///
+/// let cell = sheet.read().cell(1, 0);
+/// sheet.write().insert_column(0, |r| -(r as i64));
+/// assert!(sheet.read().cell(1, 1) == cell); // The cell moved with its column.
+///
+/// let row = sheet.read().row(1);
+/// let total = || row.share().read().iter().map(|c| *c.share().read()).sum::<i64>();
+/// assert_eq!(total(), -1 + 2 + 3);
+/// *cell.share().write() = 10;
+/// assert_eq!(total(), -1 + 10 + 3);
+///
+/// sheet.write().remove_row(1);
+/// assert!(row.share().is_detached());
+/// assert!(cell.share().is_detached());
+/// ```
+pub struct Grid<T: 'static> {
+    rows: Vec<GridLine<T>>,
+    columns: Vec<GridLine<T>>,
+    /// The watcher of each cell, which updates the cell's row and column.
+    watchers: EntryWatchers<T>,
+}
+
+/// A row or column of a [`Grid`].
+///
+/// `GridLine` implements [`PartialEq`] as a pointer, like [`ListEntry`].
+pub struct GridLine<T: 'static>(Arc<Link<Vec<ListEntry<T>>>>);
+impl<T: 'static> Clone for GridLine<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+impl<T: 'static> PartialEq for GridLine<T> {
+    fn eq(&self, o: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &o.0)
+    }
+}
+impl<T: 'static> GridLine<T> {
+    fn new(cells: Vec<ListEntry<T>>) -> Self {
+        Self(Arc::new(Link::new(cells)))
+    }
+    /// Get a read pointer to the cells of the line.
+    ///
+    /// Scope `cx` will be registered as needing update every time a cell of the line changes, and
+    /// every time cells are added to or removed from the line.
+    ///
+    /// The line itself can only be changed through the [`Grid`]; the cells it holds can be written
+    /// as usual.
+    pub fn use_rw<'a, P>(
+        &self,
+        cx: &dioxus_core::Scope<'a, P>,
+    ) -> &'a ReadOnly<Vec<ListEntry<T>>, crate::RW> {
+        let mut opt = Shareable(Some(self.0.clone()));
+        ReadOnly::new(Shared::init(cx, &mut opt, || unreachable!(), crate::RW))
+    }
+    /// Get a read pointer to the cells of the line without registering a hook.
+    pub fn share(&self) -> ReadOnly<Vec<ListEntry<T>>, crate::W> {
+        ReadOnly::from_link(self.0.clone())
+    }
+}
+
+impl<T: 'static + Send + Sync> Grid<T> {
+    /// Create an empty grid.
+    pub fn new() -> Self {
+        Self {
+            rows: Vec::new(),
+            columns: Vec::new(),
+            watchers: EntryWatchers::new(),
+        }
+    }
+    /// Create a grid with `rows` rows and `columns` columns, where the cell in row `r` and column
+    /// `c` is `f(r, c)`.
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(rows: usize, columns: usize, mut f: F) -> Self {
+        let cells = (0..rows)
+            .map(|r| (0..columns).map(|c| ListEntry::new(f(r, c))).collect())
+            .collect::<Vec<Vec<_>>>();
+        let mut grid = Self::new();
+        grid.columns = (0..columns)
+            .map(|c| GridLine::new(cells.iter().map(|row| row[c].clone()).collect()))
+            .collect();
+        grid.rows = cells.into_iter().map(GridLine::new).collect();
+        for r in 0..rows {
+            for c in 0..columns {
+                grid.watch(r, c);
+            }
+        }
+        grid
+    }
+    /// The number of rows in the grid.
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+    /// The number of columns in the grid.
+    pub fn column_count(&self) -> usize {
+        self.columns.len()
+    }
+    /// Get the cell in row `r` and column `c`.
+    pub fn get(&self, r: usize, c: usize) -> Option<ListEntry<T>> {
+        self.rows.get(r)?.0.borrow().get(c).cloned()
+    }
+    /// Get the cell in row `r` and column `c`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r` or `c` is out of bounds.
+    pub fn cell(&self, r: usize, c: usize) -> ListEntry<T> {
+        self.get(r, c).unwrap_or_else(|| {
+            panic!(
+                "cell ({r}, {c}) is out of bounds for a {}x{} grid",
+                self.rows.len(),
+                self.columns.len()
+            )
+        })
+    }
+    /// Get row `r` of the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r` is out of bounds.
+    pub fn row(&self, r: usize) -> GridLine<T> {
+        self.rows[r].clone()
+    }
+    /// Get column `c` of the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `c` is out of bounds.
+    pub fn column(&self, c: usize) -> GridLine<T> {
+        self.columns[c].clone()
+    }
+    /// Iterate over the rows of the grid.
+    pub fn rows(&self) -> std::iter::Cloned<std::slice::Iter<'_, GridLine<T>>> {
+        self.rows.iter().cloned()
+    }
+    /// Iterate over the columns of the grid.
+    pub fn columns(&self) -> std::iter::Cloned<std::slice::Iter<'_, GridLine<T>>> {
+        self.columns.iter().cloned()
+    }
+    /// Insert a row at position `index`, where the cell in column `c` is `f(c)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of rows.
+    pub fn insert_row<F: FnMut(usize) -> T>(&mut self, index: usize, mut f: F) {
+        let cells = (0..self.columns.len())
+            .map(|c| ListEntry::new(f(c)))
+            .collect::<Vec<_>>();
+        for (column, cell) in self.columns.iter().zip(&cells) {
+            column.0.borrow_mut().insert(index, cell.clone());
+            column.0.needs_update();
+        }
+        self.rows.insert(index, GridLine::new(cells));
+        for c in 0..self.columns.len() {
+            self.watch(index, c);
+        }
+    }
+    /// Insert a column at position `index`, where the cell in row `r` is `f(r)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of columns.
+    pub fn insert_column<F: FnMut(usize) -> T>(&mut self, index: usize, mut f: F) {
+        let cells = (0..self.rows.len())
+            .map(|r| ListEntry::new(f(r)))
+            .collect::<Vec<_>>();
+        for (row, cell) in self.rows.iter().zip(&cells) {
+            row.0.borrow_mut().insert(index, cell.clone());
+            row.0.needs_update();
+        }
+        self.columns.insert(index, GridLine::new(cells));
+        for r in 0..self.rows.len() {
+            self.watch(r, index);
+        }
+    }
+    /// Append a row to the grid, where the cell in column `c` is `f(c)`.
+    pub fn push_row<F: FnMut(usize) -> T>(&mut self, f: F) {
+        self.insert_row(self.rows.len(), f)
+    }
+    /// Append a column to the grid, where the cell in row `r` is `f(r)`.
+    pub fn push_column<F: FnMut(usize) -> T>(&mut self, f: F) {
+        self.insert_column(self.columns.len(), f)
+    }
+    /// Remove row `index` from the grid, returning its cells.
+    ///
+    /// The row and its cells are [detached](Shared::is_detached).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_row(&mut self, index: usize) -> Vec<Shared<T, crate::W>> {
+        let row = self.rows.remove(index);
+        for column in &self.columns {
+            column.0.borrow_mut().remove(index);
+            column.0.needs_update();
+        }
+        self.detach(row)
+    }
+    /// Remove column `index` from the grid, returning its cells.
+    ///
+    /// The column and its cells are [detached](Shared::is_detached).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_column(&mut self, index: usize) -> Vec<Shared<T, crate::W>> {
+        let column = self.columns.remove(index);
+        for row in &self.rows {
+            row.0.borrow_mut().remove(index);
+            row.0.needs_update();
+        }
+        self.detach(column)
+    }
+
+    /// Register the watcher for the cell in row `r` and column `c`.
+    fn watch(&mut self, r: usize, c: usize) {
+        let cell = self.rows[r].0.borrow()[c].clone();
+        let row = Arc::downgrade(&self.rows[r].0);
+        let column = Arc::downgrade(&self.columns[c].0);
+        self.watchers.watch(&cell, (), move |_| {
+            for line in [&row, &column] {
+                if let Some(line) = Weak::upgrade(line) {
+                    line.needs_update()
+                }
+            }
+        });
+    }
+    /// Detach a removed line and its cells.
+    fn detach(&mut self, line: GridLine<T>) -> Vec<Shared<T, crate::W>> {
+        let cells = std::mem::take(&mut *line.0.borrow_mut());
+        line.0.detach();
+        cells
+            .into_iter()
+            .map(|cell| {
+                self.watchers.unwatch(&cell);
+                cell.0.detach();
+                cell.share()
+            })
+            .collect()
+    }
+}
+impl<T: 'static + Send + Sync> Default for Grid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: 'static> Drop for Grid<T> {
+    fn drop(&mut self) {
+        self.watchers.clear();
+        for row in &self.rows {
+            for cell in row.0.borrow().iter() {
+                cell.0.detach();
+            }
+        }
+        for line in self.rows.iter().chain(&self.columns) {
+            line.0.detach();
+        }
+    }
+}
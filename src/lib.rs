@@ -5,6 +5,11 @@
 //! model, and some extensions for shared structures.
 //!
 //! The primary interfaces for the crate are [`Shared`], [`shareable_struct`] and [`List`]
+//! (along with [`Ring`], a bounded list for logs and feeds, and [`Grid`], a table of values.)
+//! Values which are loaded asynchronously can be shared with [`shareable_resource`].
+//!
+//! With the `serde` feature enabled, [`List`], [`ListEntry`] and [`Shared`] implement
+//! `Serialize` and `Deserialize` (by value.)
 //!
 //! `dioxus` is still under development; if you're using the latest nightly version of `dioxus`
 //! then your `Cargo.toml` should look something like this:
@@ -21,17 +26,90 @@
 //! ```
 
 pub mod shared;
-pub use shared::Shared;
+pub use shared::{Optimistic, ReadOnly, Shared};
 
 pub mod list;
-pub use list::{List, ListEntry};
+pub use list::{
+    List, ListAggregate, ListChange, ListEntry, ListIndex, ListOps, ListSlice, ListSliceMut,
+    ListView,
+};
+
+pub mod ring;
+pub use ring::Ring;
+
+pub mod grid;
+pub use grid::{Grid, GridLine};
+
+pub mod resource;
 
 #[doc(hidden)]
 pub mod r#struct;
 
+/// mod `attr` - the attribute form of [`shareable_struct`].
+///
+/// `#[shareable_struct]` turns an ordinary struct definition into the same shared struct as
+/// [`shareable_struct!`](shareable_struct), so it can be formatted, documented and analyzed like
+/// any other Rust item. (A `derive` can't do this, since it can't replace the struct it's applied
+/// to.)
+///
+/// Fields are initialized with `#[shareable(init = ..)]` (or with `Default::default()` when
+/// there's no `init`), computed fields are declared with `#[shareable(computed = |s| .., from =
+/// [..])]`, substructs are marked with `#[shareable(substruct)]`, and actions are
+/// declared on the struct with `#[shareable(action(NAME = "..."))]`, using the same syntax as the
+/// right hand side of an `action` in [`shareable_struct!`](shareable_struct). Other attributes on
+/// fields, e.g. doc comments, are applied to the accessor methods.
+/// ```
+/// # use dioxus::prelude::*;
+/// use dioxus_shareables::attr::shareable_struct;
+///
+/// #[shareable_struct]
+/// #[shareable(action(A impl pub ATrait = "W[a] RW[b]", pub B: BType = "W[b] RW[a, c]"))]
+/// #[shareable(action(WRITE = "W[a, b]"))]
+/// pub struct GlobalState {
+///     /// The number of things.
+///     #[shareable(init = 8)]
+///     a: usize,
+///     #[shareable(init = 12)]
+///     b: u16,
+///     c: Vec<u8>,
+/// }
+///
+/// #[shareable_struct]
+/// #[shareable(action(UA = "W[u] |s[A]"))]
+/// pub struct MoreGlobalState {
+///     #[shareable(init = "more global? more state? which is it?!".into())]
+///     u: String,
+///     #[shareable(substruct)]
+///     s: GlobalState,
+/// }
+///
+/// impl<Actions: GlobalStateActions> GlobalState<Actions> {
+///     pub fn bump(&self) where Actions: ATrait {
+///         *self.with_actions(A).a().write() += 1;
+///     }
+/// }
+///
+/// #[allow(non_snake_case)]
+/// fn Component(cx: Scope) -> Element {
+///     let state = MoreGlobalState::use_(&cx, UA);
+///     state.s().bump();
+///     let b = *state.s().b().read();
+///     cx.render(rsx! { "{b}" })
+/// }
+///
+/// let mut dom = VirtualDom::new(Component);
+/// let _ = dom.rebuild();
+/// let state = GlobalState::share(WRITE);
+/// assert_eq!((*state.a().read(), *state.b().read()), (9, 12));
+/// ```
+pub mod attr {
+    pub use dioxus_shareables_macros::shareable_struct_attr as shareable_struct;
+}
+
 #[doc(hidden)]
 pub mod reexported {
     pub use dioxus_core::Scope;
+    pub use dioxus_shareables_macros::{shareable_struct, struct_actions, struct_assoc_type};
     pub use parking_lot::Mutex;
     pub use paste::paste;
 }
@@ -77,24 +155,24 @@ mod sealed {
     );
 
     pub trait InitType: Sized + Copy {
-        fn __init_field<P, T, S: crate::shared::Static<Type = T>>(
+        fn __init_field<P, T, S: FnOnce() -> crate::Shared<T, crate::W>>(
             cx: &dioxus_core::Scope<P>,
             _: &mut Option<crate::shared::Shared<T, Self>>,
             _: S,
         );
-        fn __share_field<T, S: crate::shared::Static<Type = T>>(
+        fn __share_field<T, S: FnOnce() -> crate::Shared<T, crate::W>>(
             _: &mut Option<crate::Shared<T, Self>>,
             _: S,
         );
     }
     impl InitType for () {
-        fn __init_field<P, T, S: crate::shared::Static<Type = T>>(
+        fn __init_field<P, T, S: FnOnce() -> crate::Shared<T, crate::W>>(
             _: &dioxus_core::Scope<P>,
             _: &mut Option<crate::Shared<T, Self>>,
             _: S,
         ) {
         }
-        fn __share_field<T, S: crate::shared::Static<Type = T>>(
+        fn __share_field<T, S: FnOnce() -> crate::Shared<T, crate::W>>(
             _: &mut Option<crate::Shared<T, Self>>,
             _: S,
         ) {
@@ -119,14 +197,26 @@ pub trait InitType: sealed::InitType {
         f: &mut Option<Shared<T, Self>>,
         s: S,
     ) {
-        Self::__init_field(cx, f, s)
+        Self::__init_field(cx, f, || s._share())
+    }
+    #[doc(hidden)]
+    fn init_field_from<P, T>(
+        cx: &dioxus_core::Scope<P>,
+        f: &mut Option<Shared<T, Self>>,
+        s: &Shared<T, W>,
+    ) {
+        Self::__init_field(cx, f, || s.clone())
     }
     #[doc(hidden)]
     fn share_field<T, S: crate::shared::Static<Type = T>>(
         f: &mut Option<crate::Shared<T, Self>>,
         s: S,
     ) {
-        Self::__share_field(f, s)
+        Self::__share_field(f, || s._share())
+    }
+    #[doc(hidden)]
+    fn share_field_from<T>(f: &mut Option<crate::Shared<T, Self>>, s: &Shared<T, W>) {
+        Self::__share_field(f, || s.clone())
     }
 }
 impl<T: sealed::InitType> InitType for T {}
@@ -140,21 +230,18 @@ impl sealed::Flag for W {
     const READ: bool = false;
 }
 impl sealed::InitType for W {
-    fn __init_field<P, T, S: shared::Static<Type = T>>(
+    fn __init_field<P, T, S: FnOnce() -> Shared<T, W>>(
         _: &dioxus_core::Scope<P>,
         f: &mut Option<Shared<T, Self>>,
         s: S,
     ) {
         if f.is_none() {
-            *f = Some(s._share());
+            *f = Some(s());
         }
     }
-    fn __share_field<T, S: crate::shared::Static<Type = T>>(
-        f: &mut Option<crate::Shared<T, Self>>,
-        s: S,
-    ) {
+    fn __share_field<T, S: FnOnce() -> Shared<T, W>>(f: &mut Option<crate::Shared<T, Self>>, s: S) {
         if f.is_none() {
-            *f = Some(s._share());
+            *f = Some(s());
         }
     }
 }
@@ -169,25 +256,22 @@ impl sealed::Flag for RW {
     const READ: bool = true;
 }
 impl sealed::InitType for RW {
-    fn __init_field<P, T, S: shared::Static<Type = T>>(
+    fn __init_field<P, T, S: FnOnce() -> Shared<T, W>>(
         cx: &dioxus_core::Scope<P>,
         f: &mut Option<Shared<T, Self>>,
         s: S,
     ) {
         if f.is_none() {
             let id = cx.scope_id().0;
-            let mut r = s._share();
+            let mut r = s();
             r.id = Some(id);
             r.link.add_listener(id, || cx.schedule_update());
             // SAFETY:
             //   * Shared<T, W> and Shared<T, RW> are layed out identically in memory.
-            *f = Some(unsafe { std::mem::transmute(r) });
+            *f = Some(unsafe { std::mem::transmute::<Shared<T, W>, Shared<T, RW>>(r) });
         }
     }
-    fn __share_field<T, S: crate::shared::Static<Type = T>>(
-        _: &mut Option<crate::Shared<T, Self>>,
-        _: S,
-    ) {
+    fn __share_field<T, S: FnOnce() -> Shared<T, W>>(_: &mut Option<crate::Shared<T, Self>>, _: S) {
         unreachable!()
     }
 }
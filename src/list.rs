@@ -2,8 +2,26 @@
 //!
 //! See [`List`] for more info.
 
-use crate::shared::{Link, Shareable, Shared};
-use std::sync::Arc;
+use crate::shared::{next_watcher_id, run_watcher, Link, Shareable, Shared};
+use parking_lot::Mutex;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::hash_map::Entry;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+
+pub mod aggregate;
+mod deep;
+pub mod edit;
+pub mod index;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod slice;
+pub mod view;
+pub use aggregate::ListAggregate;
+pub use edit::{ListChange, ListOps};
+pub use index::ListIndex;
+pub use slice::{ListSlice, ListSliceIndex, ListSliceMut};
+pub use view::ListView;
 
 /// A list of shareable values.
 ///
@@ -22,15 +40,18 @@ use std::sync::Arc;
 ///                                     // removed from the list, but not when the individual list
 ///                                     // items change.
 ///     let w = nums.clone();
+///     // The sum is kept up to date as entries change, rather than recomputed on each click.
+///     let sum = cx
+///         .use_hook(|| nums.aggregate(0, |s, n| *s += n, |s, n| *s -= n))
+///         .clone();
 ///     cx.render(rsx! {
 ///         ul {
 ///             nums.read().iter().map(|n| rsx! { ListItem { num: n } })
 ///         }
 ///         button {
 ///             onclick: move |_| {
-///                 let mut w = w.write();
-///                 let sum = w.iter().map(|n| *n.share().read()).sum();
-///                 w.push(sum)
+///                 let sum = *sum.share().read();
+///                 w.write().push(sum)
 ///             },
 ///             "Sum"
 ///         }
@@ -60,7 +81,299 @@ use std::sync::Arc;
 /// `List` is a [`Vec`] internally, and the methods it implements therefore get their names and
 /// behavior from [`Vec`].
 ///
-pub struct List<T>(Vec<ListEntry<T>>);
+/// Filtered and sorted views of a shared list which stay up to date as the list changes can be
+/// created with [`view`](Shared::view), values computed from its entries (sums, counts, etc.) with
+/// [`aggregate`](Shared::aggregate), and indexes for finding entries by key with
+/// [`index_by`](Shared::index_by). Several changes can be made at once with [`edit`](Self::edit).
+///
+/// A component which needs to be updated whenever _any_ entry of a shared list changes (e.g. to
+/// display a total) can use `use_rw_deep` instead of `use_rw`:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// use dioxus_shareables::{shareable, List};
+///
+/// shareable!(Prices: List<usize> = [3, 5, 7].into_iter().collect());
+///
+/// #[allow(non_snake_case)]
+/// fn Total(cx: Scope) -> Element {
+///     let prices = Prices.use_rw_deep(&cx); // This component is updated when entries are added to
+///                                           // or removed from the list, and also when any of
+///                                           // their values change.
+///     let total: usize = prices.read().iter().map(|p| *p.share().read()).sum();
+///     cx.render(rsx! { "Total: {total}" })
+/// }
+/// # let mut dom = VirtualDom::new(Total);
+/// # let _ = dom.rebuild();
+/// # let entry = |i| Prices.share().read().get(i).unwrap().share();
+/// # assert_eq!(entry(0).listeners(), "[(0, 1)]");
+/// # Prices.share().write().push(9);
+/// # assert_eq!(entry(3).listeners(), "[(0, 1)]");
+/// # *Prices.share().write() = [1].into_iter().collect();
+/// # assert_eq!(entry(0).listeners(), "[(0, 1)]");
+/// # drop(dom);
+/// # assert_eq!(entry(0).listeners(), "[]");
+/// ```
+///
+pub struct List<T>(Vec<ListEntry<T>>, Changes<T>);
+
+/// Something which needs to know when entries are added to or removed from a [`List`].
+pub(crate) trait ListObserver<T>: Send + Sync {
+    fn added(&self, entries: &[ListEntry<T>]);
+    fn removed(&self, entries: &[ListEntry<T>]);
+}
+
+/// The entries added to and removed from a list which some of its observers haven't been told
+/// about yet.
+///
+/// Changes are only recorded while the list is observed, and are dropped once every observer has
+/// read them.
+pub(crate) struct Changes<T> {
+    /// Unique to each list, so that observers can tell when the list they observe is replaced.
+    id: usize,
+    log: Mutex<ChangeLog<T>>,
+}
+struct ChangeLog<T> {
+    /// The position of the first change in `changes`, counting from the creation of the list.
+    start: usize,
+    /// Each change, as `(added, entry)`.
+    changes: Vec<(bool, ListEntry<T>)>,
+    /// How far each observer has read. An observer drops its token when it stops reading.
+    cursors: Vec<(Weak<()>, usize)>,
+}
+impl<T> Changes<T> {
+    fn new() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            log: Mutex::new(ChangeLog {
+                start: 0,
+                changes: Vec::new(),
+                cursors: Vec::new(),
+            }),
+        }
+    }
+    fn record(&mut self, added: bool, entries: &[ListEntry<T>]) {
+        let log = self.log.get_mut();
+        log.cursors.retain(|(token, _)| token.strong_count() > 0);
+        if !log.cursors.is_empty() {
+            log.changes
+                .extend(entries.iter().map(|l| (added, l.clone())));
+        }
+    }
+    fn added(&mut self, entries: &[ListEntry<T>]) {
+        self.record(true, entries)
+    }
+    fn removed(&mut self, entries: &[ListEntry<T>]) {
+        self.record(false, entries)
+    }
+    /// Record that `entries` were removed, and mark them as detached.
+    fn detached(&mut self, entries: &[ListEntry<T>]) {
+        self.removed(entries);
+        detach(entries)
+    }
+}
+
+/// The registration of a [`ListObserver`] with the link which holds a list.
+///
+/// Observers watch the link rather than the list itself, so that they keep working however the
+/// list is changed: each time the list is written to, the observer is told about the entries
+/// which the list recorded as added or removed. If the list was replaced altogether (e.g. with
+/// `*list.write() = ..`), the observer compares the entries it knows of with the new list instead.
+/// The observer is unregistered when this is dropped.
+pub(crate) struct ListObservation<T> {
+    list: Weak<Link<List<T>>>,
+    id: usize,
+}
+/// What an observer has been told about a list.
+struct Reader<T> {
+    /// The id of the list which was read, and how far.
+    list: Option<(usize, Arc<()>, usize)>,
+    known: FxHashMap<usize, ListEntry<T>>,
+}
+impl<T> Reader<T> {
+    /// Read the changes to `list` since it was last read, as runs of `(added, entries)`.
+    fn read(&mut self, list: &List<T>) -> Vec<(bool, Vec<ListEntry<T>>)> {
+        let mut log = list.1.log.lock();
+        let mut runs = Vec::<(bool, Vec<ListEntry<T>>)>::new();
+        let mut push = |added: bool, l: &ListEntry<T>| match runs.last_mut() {
+            Some((a, run)) if *a == added => run.push(l.clone()),
+            _ => runs.push((added, vec![l.clone()])),
+        };
+        match &mut self.list {
+            Some((id, token, pos)) if *id == list.1.id => {
+                for (added, l) in &log.changes[*pos - log.start..] {
+                    let redundant = if *added {
+                        self.known.insert(l.key(), l.clone()).is_some()
+                    } else {
+                        self.known.remove(&l.key()).is_none()
+                    };
+                    if !redundant {
+                        push(*added, l)
+                    }
+                }
+                *pos = log.start + log.changes.len();
+                for (t, p) in &mut log.cursors {
+                    if std::ptr::eq(t.as_ptr(), Arc::as_ptr(token)) {
+                        *p = *pos
+                    }
+                }
+            }
+            _ => {
+                let current = list.0.iter().map(ListEntry::key).collect::<FxHashSet<_>>();
+                self.known.retain(|k, l| {
+                    let keep = current.contains(k);
+                    if !keep {
+                        push(false, l)
+                    }
+                    keep
+                });
+                for l in &list.0 {
+                    if let Entry::Vacant(e) = self.known.entry(l.key()) {
+                        e.insert(l.clone());
+                        push(true, l)
+                    }
+                }
+                let token = Arc::new(());
+                let pos = log.start + log.changes.len();
+                log.cursors.push((Arc::downgrade(&token), pos));
+                self.list = Some((list.1.id, token, pos));
+            }
+        }
+        // Drop the changes which every observer has read.
+        log.cursors.retain(|(token, _)| token.strong_count() > 0);
+        let end = log.start + log.changes.len();
+        let read = log.cursors.iter().map(|(_, p)| *p).min().unwrap_or(end);
+        let start = log.start;
+        log.changes.drain(..read - start);
+        log.start = read;
+        runs
+    }
+}
+impl<T: 'static + Send + Sync> ListObservation<T> {
+    pub(crate) fn new(list: &Arc<Link<List<T>>>) -> Self {
+        Self {
+            list: Arc::downgrade(list),
+            id: next_watcher_id(),
+        }
+    }
+    /// Tell `observer` about the entries of the list, now and each time they change.
+    pub(crate) fn start(&self, observer: Weak<dyn ListObserver<T>>) {
+        let Some(link) = self.list.upgrade() else {
+            return;
+        };
+        let list = self.list.clone();
+        let reader = Mutex::new(Reader {
+            list: None,
+            known: FxHashMap::default(),
+        });
+        let sync: Arc<dyn Send + Sync + Fn()> = Arc::new(move || {
+            let (Some(list), Some(observer)) = (list.upgrade(), observer.upgrade()) else {
+                return;
+            };
+            let runs = reader.lock().read(&list.borrow());
+            for (added, entries) in runs {
+                if added {
+                    observer.added(&entries)
+                } else {
+                    observer.removed(&entries)
+                }
+            }
+        });
+        link.add_watcher_as(self.id, sync.clone());
+        run_watcher(self.id, sync)
+    }
+}
+impl<T> Drop for ListObservation<T> {
+    fn drop(&mut self) {
+        if let Some(list) = self.list.upgrade() {
+            list.drop_watcher(self.id)
+        }
+    }
+}
+
+/// Mark entries which were removed from a list as detached.
+pub(crate) fn detach<T, L: std::borrow::Borrow<ListEntry<T>>>(
+    entries: impl IntoIterator<Item = L>,
+) {
+    for l in entries {
+        l.borrow().0.detach()
+    }
+}
+
+/// The watchers kept on list entries by something derived from them (a view, an aggregate, the
+/// lines of a grid, etc.), along with a value for each entry.
+///
+/// A watcher only holds weak pointers, so that neither the entry nor whatever watches it keeps the
+/// other alive. The watchers are removed when this is dropped.
+pub(crate) struct EntryWatchers<T, V = ()>(FxHashMap<usize, (Weak<Link<T>>, usize, V)>);
+impl<T, V> EntryWatchers<T, V> {
+    pub(crate) fn new() -> Self {
+        Self(FxHashMap::default())
+    }
+    /// Stop watching every entry.
+    pub(crate) fn clear(&mut self) {
+        for (entry, id, _) in self.0.drain().map(|(_, w)| w) {
+            if let Some(entry) = entry.upgrade() {
+                entry.drop_watcher(id);
+            }
+        }
+    }
+}
+impl<T: 'static + Send + Sync, V> EntryWatchers<T, V> {
+    /// Watch `entry`, calling `f` with it after each change.
+    pub(crate) fn watch<F: 'static + Send + Sync + Fn(&ListEntry<T>)>(
+        &mut self,
+        entry: &ListEntry<T>,
+        value: V,
+        f: F,
+    ) {
+        let weak = Arc::downgrade(&entry.0);
+        let id = entry.0.add_watcher(Arc::new(move || {
+            if let Some(entry) = weak.upgrade() {
+                f(&ListEntry(entry))
+            }
+        }));
+        let prev = self
+            .0
+            .insert(entry.key(), (Arc::downgrade(&entry.0), id, value));
+        if let Some((_, id, _)) = prev {
+            entry.0.drop_watcher(id)
+        }
+    }
+    /// Watch `entry`, calling `refresh` on `me` (if it is still alive) after each change.
+    pub(crate) fn watch_for<O: 'static + Send + Sync>(
+        &mut self,
+        entry: &ListEntry<T>,
+        value: V,
+        me: &Weak<O>,
+        refresh: fn(&O, &ListEntry<T>),
+    ) {
+        let me = me.clone();
+        self.watch(entry, value, move |entry| {
+            if let Some(me) = me.upgrade() {
+                refresh(&me, entry)
+            }
+        })
+    }
+    /// Stop watching `entry`, returning its value.
+    pub(crate) fn unwatch(&mut self, entry: &ListEntry<T>) -> Option<V> {
+        let (_, id, value) = self.0.remove(&entry.key())?;
+        entry.0.drop_watcher(id);
+        Some(value)
+    }
+    pub(crate) fn contains(&self, entry: &ListEntry<T>) -> bool {
+        self.0.contains_key(&entry.key())
+    }
+    pub(crate) fn get_mut(&mut self, entry: &ListEntry<T>) -> Option<&mut V> {
+        self.0.get_mut(&entry.key()).map(|(_, _, v)| v)
+    }
+}
+impl<T, V> Drop for EntryWatchers<T, V> {
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
 
 #[allow(non_camel_case_types)]
 pub type share_entry_w<T> = fn(ListEntry<T>) -> Shared<T, super::W>;
@@ -68,6 +381,8 @@ pub type Drain<'a, T> = std::iter::Map<std::vec::Drain<'a, ListEntry<T>>, share_
 impl<T> List<T> {
     /// See [`Vec::append`]
     pub fn append(&mut self, o: &mut Self) {
+        o.1.removed(&o.0);
+        self.1.added(&o.0);
         self.0.append(&mut o.0)
     }
     /// See [`Vec::capacity`]
@@ -76,33 +391,79 @@ impl<T> List<T> {
     }
     /// See [`Vec::clear`]
     pub fn clear(&mut self) {
+        self.1.detached(&self.0);
         self.0.clear()
     }
     /// See [`Vec::dedup`]
-    pub fn dedup(&mut self)
+    ///
+    /// Returns the removed entries.
+    pub fn dedup(&mut self) -> Vec<Shared<T, super::W>>
     where
-        T: PartialEq,
+        T: PartialEq + 'static,
     {
         self.dedup_by(PartialEq::eq)
     }
     /// See [`Vec::dedup_by`]
-    pub fn dedup_by<F: FnMut(&T, &T) -> bool>(&mut self, mut f: F) {
-        self.0.dedup_by(|r, s| f(&r.0.borrow(), &s.0.borrow()))
+    ///
+    /// Returns the removed entries.
+    pub fn dedup_by<F: FnMut(&T, &T) -> bool>(&mut self, mut f: F) -> Vec<Shared<T, super::W>>
+    where
+        T: 'static,
+    {
+        let mut removed = Vec::new();
+        self.0.dedup_by(|r, s| {
+            let dup = f(&r.0.borrow(), &s.0.borrow());
+            if dup {
+                removed.push(r.clone());
+            }
+            dup
+        });
+        self.1.detached(&removed);
+        removed
+            .into_iter()
+            .map(|l| Shared::from_link(l.0))
+            .collect()
     }
     /// See [`Vec::dedup_by_key`]
-    pub fn dedup_by_key<K: PartialEq, F: FnMut(&T) -> K>(&mut self, mut f: F) {
-        self.0.dedup_by(|r, s| f(&r.0.borrow()) == f(&s.0.borrow()))
+    ///
+    /// Returns the removed entries.
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&T) -> K>(
+        &mut self,
+        mut f: F,
+    ) -> Vec<Shared<T, super::W>>
+    where
+        T: 'static,
+    {
+        self.dedup_by(|r, s| f(r) == f(s))
     }
     /// See [`Vec::drain`]
-    pub fn drain<R: std::ops::RangeBounds<usize>>(&mut self, range: R) -> Drain<T>
+    pub fn drain<R: std::ops::RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T>
     where
         T: 'static,
     {
+        let range = slice_range(range, self.0.len());
+        self.1.detached(&self.0[range.clone()]);
         self.0.drain(range).map(|l| Shared::from_link(l.0))
     }
+    /// See [`Vec::extend_from_within`]
+    ///
+    /// The new entries are clones of the values in `src`, not the same entries.
+    pub fn extend_from_within<R: std::ops::RangeBounds<usize>>(&mut self, src: R)
+    where
+        T: Clone,
+    {
+        let len = self.0.len();
+        for i in slice_range(src, len) {
+            let value = self.0[i].0.borrow().clone();
+            self.0.push(ListEntry::new(value));
+        }
+        self.1.added(&self.0[len..])
+    }
     /// See [`Vec::insert`]
     pub fn insert(&mut self, index: usize, element: T) {
-        self.0.insert(index, ListEntry::new(element))
+        let l = ListEntry::new(element);
+        self.1.added(std::slice::from_ref(&l));
+        self.0.insert(index, l)
     }
     /// See [`Vec::is_empty`]
     pub fn is_empty(&self) -> bool {
@@ -114,19 +475,25 @@ impl<T> List<T> {
     }
     /// See [`Vec::new`]
     pub fn new() -> Self {
-        Self(Vec::new())
+        Self(Vec::new(), Changes::new())
     }
     /// See [`Vec::pop`]
     pub fn pop(&mut self) -> Option<Shared<T, super::W>> {
-        self.0.pop().map(|l| Shared::from_link(l.0))
+        let l = self.0.pop()?;
+        self.1.detached(std::slice::from_ref(&l));
+        Some(Shared::from_link(l.0))
     }
     /// See [`Vec::push`]
     pub fn push(&mut self, value: T) {
-        self.0.push(ListEntry::new(value))
+        let l = ListEntry::new(value);
+        self.1.added(std::slice::from_ref(&l));
+        self.0.push(l)
     }
     /// See [`Vec::remove`]
     pub fn remove(&mut self, index: usize) -> Shared<T, super::W> {
-        Shared::from_link(self.0.remove(index).0)
+        let l = self.0.remove(index);
+        self.1.detached(std::slice::from_ref(&l));
+        Shared::from_link(l.0)
     }
     /// See [`Vec::reserve`]
     pub fn reserve(&mut self, additional: usize) {
@@ -141,22 +508,39 @@ impl<T> List<T> {
     where
         T: Clone,
     {
-        self.0.resize_with(new_len, || ListEntry::new(t.clone()))
+        self.resize_with(new_len, || t.clone())
     }
     /// See [`Vec::resize_with`]
     pub fn resize_with<F: FnMut() -> T>(&mut self, new_len: usize, mut f: F) {
-        self.0.resize_with(new_len, || ListEntry::new(f()))
+        let len = self.0.len();
+        if new_len > len {
+            self.0.resize_with(new_len, || ListEntry::new(f()));
+            self.1.added(&self.0[len..]);
+        } else {
+            self.truncate(new_len)
+        }
     }
     /// See [`Vec::retain`]
     pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
-        self.0.retain(|l| f(&l.0.borrow()))
+        self.retain_mut(|l| f(&l.0.borrow()))
     }
     /// See [`Vec::retain`]
-    pub fn retain_mut<F: FnMut(&mut ListEntry<T>) -> bool>(&mut self, f: F)
-    where
-        T: 'static,
-    {
-        self.0.retain_mut(f)
+    pub fn retain_mut<F: FnMut(&mut ListEntry<T>) -> bool>(&mut self, mut f: F) {
+        let mut removed = Vec::new();
+        let mut added = Vec::new();
+        self.0.retain_mut(|l| {
+            let prev = l.clone();
+            let keep = f(l);
+            if !keep || prev != *l {
+                removed.push(prev);
+                if keep {
+                    added.push(l.clone());
+                }
+            }
+            keep
+        });
+        self.1.detached(&removed);
+        self.1.added(&added)
     }
     /// See [`Vec::shrink_to`]
     pub fn shrink_to(&mut self, min_capacity: usize) {
@@ -175,21 +559,35 @@ impl<T> List<T> {
     where
         T: 'static,
     {
+        let range = slice_range(range, self.0.len());
+        let replace_with = replace_with
+            .into_iter()
+            .map(ListEntry::new)
+            .collect::<Vec<_>>();
+        self.1.detached(&self.0[range.clone()]);
+        self.1.added(&replace_with);
         self.0
-            .splice(range, replace_with.into_iter().map(ListEntry::new))
+            .splice(range, replace_with)
             .map(|l| Shared::from_link(l.0))
     }
     /// See [`Vec::split_off`]
     pub fn split_off(&mut self, at: usize) -> Self {
-        Self(self.0.split_off(at))
+        let r = self.0.split_off(at);
+        self.1.removed(&r);
+        Self(r, Changes::new())
     }
     /// See [`Vec::swap_remove`]
     pub fn swap_remove(&mut self, index: usize) -> Shared<T, super::W> {
-        Shared::from_link(self.0.swap_remove(index).0)
+        let l = self.0.swap_remove(index);
+        self.1.detached(std::slice::from_ref(&l));
+        Shared::from_link(l.0)
     }
     /// See ['Vec::truncate`]
     pub fn truncate(&mut self, len: usize) {
-        self.0.truncate(len)
+        if len < self.0.len() {
+            self.1.detached(&self.0[len..]);
+            self.0.truncate(len)
+        }
     }
     /// See ['Vec::try_reserve`]
     pub fn try_reserve(
@@ -207,7 +605,7 @@ impl<T> List<T> {
     }
     /// See ['Vec::with_capacity`]
     pub fn with_capcity(capacity: usize) -> Self {
-        Self(Vec::with_capacity(capacity))
+        Self(Vec::with_capacity(capacity), Changes::new())
     }
     /// See [`[_]::binary_search`]
     pub fn binary_search(&self, x: &T) -> Result<usize, usize>
@@ -255,20 +653,24 @@ impl<T> List<T> {
     where
         T: Clone,
     {
-        self.0.fill_with(|| ListEntry::new(t.clone()))
+        self.fill_with(|| t.clone())
     }
     /// See [`[_]::fill_with`]
     ///
     /// Note: This replaces items, rather than changing their value, so components which were
     /// linked to the list before will not (necessarily) update.
     pub fn fill_with<F: FnMut() -> T>(&mut self, mut f: F) {
-        self.0.fill_with(|| ListEntry::new(f()))
+        self.1.detached(&self.0);
+        self.0.fill_with(|| ListEntry::new(f()));
+        self.1.added(&self.0)
     }
     /// See [`[_]::first`]
     pub fn first(&self) -> Option<ListEntry<T>> {
         self.0.first().cloned()
     }
     /// See [`[_]::get`]
+    ///
+    /// To get a range of entries, use [`as_slice`](Self::as_slice).
     pub fn get(&self, index: usize) -> Option<ListEntry<T>> {
         self.0.get(index).cloned()
     }
@@ -293,15 +695,15 @@ impl<T> List<T> {
     }
     /// See [`[_]::reverse`]
     pub fn reverse(&mut self) {
-        self.0.reverse()
+        self.as_mut_slice().reverse()
     }
     /// See [`[_]::rotate_left`]
     pub fn rotate_left(&mut self, mid: usize) {
-        self.0.rotate_left(mid)
+        self.as_mut_slice().rotate_left(mid)
     }
     /// See [`[_]::rotate_right`]
     pub fn rotate_right(&mut self, mid: usize) {
-        self.0.rotate_right(mid)
+        self.as_mut_slice().rotate_right(mid)
     }
     /// See [`[_]::sort`]
     pub fn sort(&mut self)
@@ -311,16 +713,16 @@ impl<T> List<T> {
         self.sort_by(Ord::cmp)
     }
     /// See [`[_]::sort_by`]
-    pub fn sort_by<F: FnMut(&T, &T) -> std::cmp::Ordering>(&mut self, mut f: F) {
-        self.0.sort_by(|a, b| f(&a.0.borrow(), &b.0.borrow()))
+    pub fn sort_by<F: FnMut(&T, &T) -> std::cmp::Ordering>(&mut self, f: F) {
+        self.as_mut_slice().sort_by(f)
     }
     /// See [`[_]::sort_by`]
-    pub fn sort_by_cached_key<U: Ord, F: FnMut(&T) -> U>(&mut self, mut f: F) {
-        self.0.sort_by_cached_key(|a| f(&a.0.borrow()))
+    pub fn sort_by_cached_key<U: Ord, F: FnMut(&T) -> U>(&mut self, f: F) {
+        self.as_mut_slice().sort_by_cached_key(f)
     }
     /// See [`[_]::sort_by`]
-    pub fn sort_by_key<U: Ord, F: FnMut(&T) -> U>(&mut self, mut f: F) {
-        self.0.sort_by_key(|a| f(&a.0.borrow()))
+    pub fn sort_by_key<U: Ord, F: FnMut(&T) -> U>(&mut self, f: F) {
+        self.as_mut_slice().sort_by_key(f)
     }
     /// See [`[_]::sort`]
     pub fn sort_unstable(&mut self)
@@ -330,13 +732,12 @@ impl<T> List<T> {
         self.sort_unstable_by(Ord::cmp)
     }
     /// See [`[_]::sort_by`]
-    pub fn sort_unstable_by<F: FnMut(&T, &T) -> std::cmp::Ordering>(&mut self, mut f: F) {
-        self.0
-            .sort_unstable_by(|a, b| f(&a.0.borrow(), &b.0.borrow()))
+    pub fn sort_unstable_by<F: FnMut(&T, &T) -> std::cmp::Ordering>(&mut self, f: F) {
+        self.as_mut_slice().sort_unstable_by(f)
     }
     /// See [`[_]::sort_by`]
-    pub fn sort_unstable_by_key<U: Ord, F: FnMut(&T) -> U>(&mut self, mut f: F) {
-        self.0.sort_unstable_by_key(|a| f(&a.0.borrow()))
+    pub fn sort_unstable_by_key<U: Ord, F: FnMut(&T) -> U>(&mut self, f: F) {
+        self.as_mut_slice().sort_unstable_by_key(f)
     }
     /// See [`[_]::starts_with`]
     pub fn starts_with(&self, needle: &[T]) -> bool
@@ -348,7 +749,61 @@ impl<T> List<T> {
     }
     /// See [`[_]::swap`]
     pub fn swap(&mut self, a: usize, b: usize) {
-        self.0.swap(a, b)
+        self.as_mut_slice().swap(a, b)
+    }
+}
+impl<T> List<T> {
+    /// Move the entry at `index` into `other` at `other_index`.
+    ///
+    /// Unlike removing the value and inserting it into `other`, this moves the entry itself, so
+    /// components which hold the entry stay linked to it, and it is not
+    /// [detached](Shared::is_detached).
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// use dioxus_shareables::{shareable, List};
+    ///
+    /// shareable!(Todo: List<&'static str> = ["wash", "dry", "fold"].into_iter().collect());
+    /// shareable!(Done: List<&'static str> = List::new());
+    ///
+    /// let dry = Todo.share().read().get(1).unwrap();
+    /// Todo.share().write().move_to(1, &mut Done.share().write(), 0);
+    /// assert!(Done.share().read().first() == Some(dry.clone()));
+    /// assert!(!dry.share().is_detached());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds for `self` or `other_index` is greater than the length
+    /// of `other`.
+    pub fn move_to(&mut self, index: usize, other: &mut Self, other_index: usize) {
+        assert!(
+            other_index <= other.0.len(),
+            "other_index (is {other_index}) should be <= len (is {})",
+            other.0.len()
+        );
+        let l = self.0.remove(index);
+        self.1.removed(std::slice::from_ref(&l));
+        other.1.added(std::slice::from_ref(&l));
+        other.0.insert(other_index, l)
+    }
+    /// Move `entry` to the end of `other`.
+    ///
+    /// Returns `false` (and does nothing) if `entry` is not in the list.
+    ///
+    /// See [`move_to`](Self::move_to) for more info.
+    pub fn transfer(&mut self, entry: &ListEntry<T>, other: &mut Self) -> bool {
+        if let Some(index) = self.0.iter().position(|l| l == entry) {
+            self.move_to(index, other, other.0.len());
+            true
+        } else {
+            false
+        }
+    }
+}
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        detach(&self.0)
     }
 }
 impl<T> Default for List<T> {
@@ -363,22 +818,61 @@ impl<'a, T> IntoIterator for &'a List<T> {
         self.0.iter().cloned()
     }
 }
+impl<T: 'static> IntoIterator for List<T> {
+    type Item = Shared<T, super::W>;
+    type IntoIter = std::iter::Map<std::vec::IntoIter<ListEntry<T>>, share_entry_w<T>>;
+    /// Remove every entry from the list, as with [`drain(..)`](Self::drain).
+    fn into_iter(mut self) -> Self::IntoIter {
+        detach(&self.0);
+        std::mem::take(&mut self.0)
+            .into_iter()
+            .map(|l| Shared::from_link(l.0))
+    }
+}
 impl<T> FromIterator<T> for List<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        Self(iter.into_iter().map(ListEntry::new).collect())
+        Self(
+            iter.into_iter().map(ListEntry::new).collect(),
+            Changes::new(),
+        )
     }
 }
 impl<T> Extend<T> for List<T> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        self.0.extend(iter.into_iter().map(ListEntry::new))
+        let len = self.0.len();
+        self.0.extend(iter.into_iter().map(ListEntry::new));
+        self.1.added(&self.0[len..])
     }
 }
 impl<'a, T: 'a + Clone> Extend<&'a T> for List<T> {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
-        self.0.extend(iter.into_iter().cloned().map(ListEntry::new))
+        self.extend(iter.into_iter().cloned())
     }
 }
 
+/// Resolve a `RangeBounds` into a `Range` for a slice of length `len`.
+///
+/// Panics in the same cases that slice indexing would.
+fn slice_range<R: std::ops::RangeBounds<usize>>(range: R, len: usize) -> std::ops::Range<usize> {
+    use std::ops::Bound;
+    let start = match range.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s.checked_add(1).expect("range start overflowed"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&e) => e.checked_add(1).expect("range end overflowed"),
+        Bound::Excluded(&e) => e,
+        Bound::Unbounded => len,
+    };
+    assert!(start <= end, "range start {start} is after range end {end}");
+    assert!(
+        end <= len,
+        "range end {end} is out of bounds for length {len}"
+    );
+    start..end
+}
+
 /// A pointer to an element from a [`List`]
 ///
 /// Note that this cannot be used directly to get access to the value in the list. Instead, one
@@ -386,7 +880,10 @@ impl<'a, T: 'a + Clone> Extend<&'a T> for List<T> {
 ///
 /// `ListEntry` implements [`PartialEq`] _AS A POINTER ONLY_. This is so that the properties of a
 /// component depend only on which list entry is referenced, and not on the value.
-pub struct ListEntry<T>(Arc<Link<T>>);
+///
+/// When an entry is removed from its list, components which hold a RW pointer to it are marked as
+/// needing update, and [`Shared::is_detached`] returns `true` for it.
+pub struct ListEntry<T>(pub(crate) Arc<Link<T>>);
 impl<T> PartialEq for ListEntry<T> {
     fn eq(&self, o: &Self) -> bool {
         Arc::ptr_eq(&self.0, &o.0)
@@ -398,17 +895,32 @@ impl<T> Clone for ListEntry<T> {
     }
 }
 impl<T> ListEntry<T> {
-    fn new(t: T) -> Self {
+    pub(crate) fn new(t: T) -> Self {
         ListEntry(Arc::new(Link::new(t)))
     }
+    /// A key identifying the entry (by pointer.)
+    pub(crate) fn key(&self) -> usize {
+        Arc::as_ptr(&self.0) as usize
+    }
     /// Get a write-only pointer to the element.
     ///
-    /// This is generally how an entry is accessed from the component which owns its `List`.
+    /// This is generally how an entry is accessed from the component which owns its `List`; if
+    /// that component displays the value, use [`subscribe`](Self::subscribe) instead.
     /// If the entry was passed down from a parent component, then you generally want to call
     /// [`use_w`](Self::use_w) or [`use_rw`](Self::use_rw) instead.
     pub fn share(&self) -> Shared<T, super::W> {
         Shared::from_link(self.0.clone())
     }
+    /// Get a write-only pointer to the element, and mark scope `cx` as needing update the next
+    /// time the element changes.
+    ///
+    /// This is not a hook, so it can be used by the component which owns the list to read
+    /// entries inside a loop. See [`Shared::subscribe_scope`] for more info.
+    pub fn subscribe<P>(&self, cx: &dioxus_core::Scope<P>) -> Shared<T, super::W> {
+        let r = self.share();
+        r.subscribe_scope(cx);
+        r
+    }
     /// Get a write pointer to the element as a hook.
     ///
     /// This is the expected way to get write-only access to an entry when it is passed down from a
@@ -0,0 +1,189 @@
+//! mod `aggregate` - values computed incrementally from lists.
+//!
+//! See [`ListAggregate`] for more info.
+
+use super::{EntryWatchers, List, ListEntry, ListObservation, ListObserver};
+use crate::shared::{Link, Shareable, Shared};
+use parking_lot::Mutex;
+use std::sync::{Arc, Weak};
+
+type AggregateFn<A, T> = Box<dyn Send + FnMut(&mut A, &T)>;
+
+/// A value computed from the entries of a [`List`], which is kept up to date incrementally.
+///
+/// An aggregate is described by an initial value and two functions: `fold`, which adds an entry's
+/// value to the aggregate, and `unfold`, which takes it back out. When an entry is added to the
+/// list it is folded in, when it is removed it is unfolded, and when its value changes the old
+/// value is unfolded and the new value folded in. The rest of the list is never revisited.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// use dioxus_shareables::{shareable, List};
+///
+/// shareable!(Numbers: List<usize> = [3, 5, 7].into_iter().collect());
+///
+/// #[allow(non_snake_case)]
+/// fn Sum(cx: Scope) -> Element {
+///     // The aggregate must be kept alive for as long as it is in use, so we store it in a hook.
+///     let sum = cx.use_hook(|| Numbers.share().aggregate(0, |s, n| *s += n, |s, n| *s -= n));
+///     let sum = sum.use_rw(&cx); // This component is updated whenever the sum changes.
+///     cx.render(rsx! { "The sum is {sum.read()}" })
+/// }
+///
+/// let numbers = Numbers.share();
+/// let sum = numbers.aggregate(0, |s, n| *s += n, |s, n| *s -= n);
+/// assert_eq!(*sum.share().read(), 15);
+/// numbers.write().push(10);
+/// assert_eq!(*sum.share().read(), 25);
+/// *numbers.read().get(0).unwrap().share().write() = 4;
+/// assert_eq!(*sum.share().read(), 26);
+/// numbers.write().remove(1);
+/// assert_eq!(*sum.share().read(), 21);
+///
+/// // The aggregate follows the list even when it is replaced.
+/// *numbers.write() = [1, 2].into_iter().collect();
+/// assert_eq!(*sum.share().read(), 3);
+/// numbers.write().push(4);
+/// assert_eq!(*sum.share().read(), 7);
+/// ```
+///
+/// Aggregates which cannot be undone directly, such as a minimum or a maximum, can still be kept
+/// incrementally by aggregating into a multiset:
+///
+/// ```rust
+/// # use dioxus_shareables::{shareable, List};
+/// use std::collections::BTreeMap;
+///
+/// shareable!(Numbers: List<usize> = [3, 5, 7].into_iter().collect());
+///
+/// let counts = Numbers.share().aggregate(
+///     BTreeMap::new(),
+///     |m, n| *m.entry(*n).or_insert(0usize) += 1,
+///     |m, n| {
+///         let c = m.get_mut(n).unwrap();
+///         *c -= 1;
+///         if *c == 0 {
+///             m.remove(n);
+///         }
+///     },
+/// );
+/// let max = || counts.share().read().keys().next_back().copied();
+/// assert_eq!(max(), Some(7));
+/// Numbers.share().write().pop();
+/// assert_eq!(max(), Some(5));
+/// ```
+pub struct ListAggregate<T: 'static, A: 'static>(Arc<AggregateInner<T, A>>);
+impl<T: 'static, A: 'static> Clone for ListAggregate<T, A> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+struct AggregateInner<T: 'static, A: 'static> {
+    me: Weak<AggregateInner<T, A>>,
+    list: ListObservation<T>,
+    link: Arc<Link<A>>,
+    state: Mutex<AggregateState<T, A>>,
+}
+struct AggregateState<T, A> {
+    fold: AggregateFn<A, T>,
+    unfold: AggregateFn<A, T>,
+    /// The value last folded in for each entry, along with its watcher.
+    entries: EntryWatchers<T, T>,
+}
+
+impl<T: 'static + Send + Sync + Clone, B: 'static> Shared<List<T>, B> {
+    /// Create an aggregate of the entries of the list.
+    ///
+    /// The aggregate starts out as `init` with every entry folded in. See [`ListAggregate`] for
+    /// more info.
+    pub fn aggregate<A, F, U>(&self, init: A, fold: F, unfold: U) -> ListAggregate<T, A>
+    where
+        A: 'static + Send + Sync,
+        F: 'static + Send + FnMut(&mut A, &T),
+        U: 'static + Send + FnMut(&mut A, &T),
+    {
+        let inner = Arc::new_cyclic(|me| AggregateInner {
+            me: me.clone(),
+            list: ListObservation::new(&self.link),
+            link: Arc::new(Link::new(init)),
+            state: Mutex::new(AggregateState {
+                fold: Box::new(fold),
+                unfold: Box::new(unfold),
+                entries: EntryWatchers::new(),
+            }),
+        });
+        let weak: Weak<AggregateInner<T, A>> = Arc::downgrade(&inner);
+        inner.list.start(weak);
+        ListAggregate(inner)
+    }
+}
+
+impl<T: 'static, A: 'static> ListAggregate<T, A> {
+    /// Get a read-write pointer to the aggregate value.
+    ///
+    /// Scope `cx` will be registered as needing update every time the aggregate changes.
+    pub fn use_rw<'a, P>(&self, cx: &dioxus_core::Scope<'a, P>) -> &'a mut Shared<A, crate::RW> {
+        let mut opt = Shareable(Some(self.0.link.clone()));
+        Shared::init(cx, &mut opt, || unreachable!(), crate::RW)
+    }
+    /// Get a pointer to the aggregate value without registering a hook.
+    pub fn share(&self) -> Shared<A, crate::W> {
+        Shared::from_link(self.0.link.clone())
+    }
+}
+
+impl<T: 'static + Send + Sync + Clone, A: 'static + Send + Sync> AggregateInner<T, A> {
+    /// Replace the folded value of a single entry of the list.
+    fn refresh(&self, entry: &ListEntry<T>) {
+        {
+            let mut state = self.state.lock();
+            let AggregateState {
+                fold,
+                unfold,
+                entries,
+            } = &mut *state;
+            let Some(prev) = entries.get_mut(entry) else {
+                return;
+            };
+            let mut a = self.link.borrow_mut();
+            unfold(&mut a, prev);
+            *prev = entry.0.borrow().clone();
+            fold(&mut a, prev);
+        }
+        self.link.needs_update();
+    }
+}
+
+impl<T: 'static + Send + Sync + Clone, A: 'static + Send + Sync> ListObserver<T>
+    for AggregateInner<T, A>
+{
+    fn added(&self, added: &[ListEntry<T>]) {
+        {
+            let mut state = self.state.lock();
+            let AggregateState { fold, entries, .. } = &mut *state;
+            let mut a = self.link.borrow_mut();
+            for entry in added {
+                let value = entry.0.borrow().clone();
+                fold(&mut a, &value);
+                entries.watch_for(entry, value, &self.me, Self::refresh);
+            }
+        }
+        self.link.needs_update();
+    }
+    fn removed(&self, removed: &[ListEntry<T>]) {
+        {
+            let mut state = self.state.lock();
+            let AggregateState {
+                unfold, entries, ..
+            } = &mut *state;
+            let mut a = self.link.borrow_mut();
+            for entry in removed {
+                if let Some(value) = entries.unwatch(entry) {
+                    unfold(&mut a, &value);
+                }
+            }
+        }
+        self.link.needs_update();
+    }
+}
@@ -0,0 +1,71 @@
+//! mod `deep` - subscriptions to every entry of a list.
+
+use super::{List, ListEntry, ListObservation, ListObserver};
+use crate::shared::{Link, Shared, Static};
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+use std::sync::{Arc, Weak};
+
+impl<T: 'static + Send + Sync> List<T> {
+    #[doc(hidden)]
+    pub fn _use_rw_deep<'a, P, S: Static<Type = Self>>(
+        s: S,
+        cx: &dioxus_core::Scope<'a, P>,
+    ) -> &'a mut Shared<Self, crate::RW> {
+        let r = s._use_rw(cx);
+        cx.use_hook(|| DeepSubscription::new(&r.link, cx.scope_id().0, cx.schedule_update()));
+        r
+    }
+}
+
+/// Keeps a scope listening to every entry of a list for as long as it is held.
+struct DeepSubscription<T>(Arc<DeepInner<T>>);
+struct DeepInner<T> {
+    id: usize,
+    update: Arc<dyn Send + Sync + Fn()>,
+    entries: Mutex<FxHashMap<usize, Weak<Link<T>>>>,
+    list: ListObservation<T>,
+}
+impl<T: 'static + Send + Sync> DeepSubscription<T> {
+    fn new(list: &Arc<Link<List<T>>>, id: usize, update: Arc<dyn Send + Sync + Fn()>) -> Self {
+        let inner = Arc::new(DeepInner {
+            id,
+            update,
+            entries: Mutex::new(FxHashMap::default()),
+            list: ListObservation::new(list),
+        });
+        let weak: Weak<DeepInner<T>> = Arc::downgrade(&inner);
+        inner.list.start(weak);
+        Self(inner)
+    }
+}
+impl<T: Send + Sync> ListObserver<T> for DeepInner<T> {
+    fn added(&self, entries: &[ListEntry<T>]) {
+        let mut tracked = self.entries.lock();
+        for entry in entries {
+            if tracked
+                .insert(entry.key(), Arc::downgrade(&entry.0))
+                .is_none()
+            {
+                entry.0.add_listener(self.id, || self.update.clone());
+            }
+        }
+    }
+    fn removed(&self, entries: &[ListEntry<T>]) {
+        let mut tracked = self.entries.lock();
+        for entry in entries {
+            if tracked.remove(&entry.key()).is_some() {
+                entry.0.drop_listener(self.id);
+            }
+        }
+    }
+}
+impl<T> Drop for DeepSubscription<T> {
+    fn drop(&mut self) {
+        for entry in self.0.entries.lock().values() {
+            if let Some(entry) = entry.upgrade() {
+                entry.drop_listener(self.0.id);
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! mod `edit` - batched changes to lists.
+//!
+//! See [`List::edit`] for more info.
+
+use super::{List, ListEntry};
+use crate::shared::Shared;
+use rustc_hash::FxHashSet;
+
+type UpdateFn<'a, T> = Box<dyn 'a + FnOnce(&mut T)>;
+
+enum Op<'a, T> {
+    Insert(usize, T),
+    Remove(usize),
+    Move(usize, usize),
+    Update(usize, UpdateFn<'a, T>),
+}
+
+/// A batch of operations on a [`List`].
+///
+/// This is passed to the closure given to [`List::edit`]. Operations are recorded in order, and
+/// each index refers to the list as it is after the operations before it have been applied, as if
+/// they were made on a [`Vec`] one after another.
+pub struct ListOps<'a, T>(Vec<Op<'a, T>>);
+impl<'a, T> ListOps<'a, T> {
+    /// Insert `value` at position `index`.
+    pub fn insert(&mut self, index: usize, value: T) -> &mut Self {
+        self.0.push(Op::Insert(index, value));
+        self
+    }
+    /// Remove the entry at position `index`.
+    pub fn remove(&mut self, index: usize) -> &mut Self {
+        self.0.push(Op::Remove(index));
+        self
+    }
+    /// Move the entry at position `from` so that it ends up at position `to`.
+    ///
+    /// The entry itself is moved, so components which hold it stay linked to it.
+    pub fn move_entry(&mut self, from: usize, to: usize) -> &mut Self {
+        self.0.push(Op::Move(from, to));
+        self
+    }
+    /// Change the value of the entry at position `index` with `f`.
+    pub fn update<F: 'a + FnOnce(&mut T)>(&mut self, index: usize, f: F) -> &mut Self {
+        self.0.push(Op::Update(index, Box::new(f)));
+        self
+    }
+    /// Replace the value of the entry at position `index`.
+    pub fn set(&mut self, index: usize, value: T) -> &mut Self
+    where
+        T: 'a,
+    {
+        self.update(index, move |t| *t = value)
+    }
+}
+
+/// The combined changes made to a [`List`] by [`List::edit`].
+pub struct ListChange<T: 'static> {
+    /// The entries which were added to the list, in the order they appear in it.
+    pub inserted: Vec<ListEntry<T>>,
+    /// The entries which were removed from the list.
+    pub removed: Vec<Shared<T, crate::W>>,
+    /// The entries which were already in the list and whose values were updated.
+    pub updated: Vec<ListEntry<T>>,
+    /// Whether the order of the entries which were already in the list changed.
+    pub moved: bool,
+}
+
+impl<T: 'static> List<T> {
+    /// Apply a batch of operations to the list at once.
+    ///
+    /// The operations recorded by `f` are checked and applied together, so if any of them is out
+    /// of bounds nothing is changed. When the list is written through a single [`Shared::write`],
+    /// its observers ([views](Shared::view), [aggregates](Shared::aggregate), etc.) are notified
+    /// once for the whole batch, and its components are updated only once.
+    ///
+    /// ```rust
+    /// use dioxus_shareables::{shareable, List};
+    ///
+    /// shareable!(Queue: List<&'static str> = ["a", "b", "c"].into_iter().collect());
+    ///
+    /// let b = Queue.share().read().get(1).unwrap();
+    /// let change = Queue.share().write().edit(|ops| {
+    ///     ops.remove(0).insert(0, "x").move_entry(1, 2).set(0, "y").update(1, |s| *s = "z");
+    /// });
+    /// let values = Queue.share().read().iter().map(|l| *l.share().read()).collect::<Vec<_>>();
+    /// assert_eq!(values, ["y", "z", "b"]);
+    /// assert_eq!(change.inserted.len(), 1);
+    /// assert_eq!(*change.removed[0].read(), "a");
+    /// assert!(change.updated[0] != b);
+    /// assert!(change.moved);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics (before changing anything) if an operation refers to a position which is out of
+    /// bounds. The updates are run before the new entries are put in the list, so if one of them
+    /// panics the list keeps its old entries, although values changed by earlier updates stay
+    /// changed.
+    pub fn edit<'a, F: FnOnce(&mut ListOps<'a, T>)>(&mut self, f: F) -> ListChange<T> {
+        let mut ops = ListOps(Vec::new());
+        f(&mut ops);
+
+        // Apply the changes to a copy of the entries first, so that a bad index leaves the list
+        // as it was.
+        let mut entries = self.0.clone();
+        let mut updates = Vec::new();
+        for op in ops.0 {
+            match op {
+                Op::Insert(index, value) => {
+                    let len = entries.len();
+                    assert!(
+                        index <= len,
+                        "insertion index (is {index}) should be <= len (is {len})"
+                    );
+                    entries.insert(index, ListEntry::new(value))
+                }
+                Op::Remove(index) => {
+                    check_index(index, entries.len());
+                    entries.remove(index);
+                }
+                Op::Move(from, to) => {
+                    check_index(from, entries.len());
+                    check_index(to, entries.len());
+                    let l = entries.remove(from);
+                    entries.insert(to, l)
+                }
+                Op::Update(index, f) => {
+                    check_index(index, entries.len());
+                    updates.push((entries[index].clone(), f))
+                }
+            }
+        }
+
+        let old = self.0.iter().map(ListEntry::key).collect::<FxHashSet<_>>();
+        let new = entries.iter().map(ListEntry::key).collect::<FxHashSet<_>>();
+        let removed = self
+            .0
+            .iter()
+            .filter(|l| !new.contains(&l.key()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let inserted = entries
+            .iter()
+            .filter(|l| !old.contains(&l.key()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let moved = self
+            .0
+            .iter()
+            .filter(|l| new.contains(&l.key()))
+            .ne(entries.iter().filter(|l| old.contains(&l.key())));
+
+        let mut updated = Vec::new();
+        let mut seen = FxHashSet::default();
+        for (l, f) in updates {
+            f(&mut l.0.borrow_mut());
+            if old.contains(&l.key()) && new.contains(&l.key()) && seen.insert(l.key()) {
+                updated.push(l)
+            }
+        }
+        self.0 = entries;
+        self.1.detached(&removed);
+        self.1.added(&inserted);
+        for l in &updated {
+            l.0.needs_update()
+        }
+        ListChange {
+            inserted,
+            removed: removed
+                .into_iter()
+                .map(|l| Shared::from_link(l.0))
+                .collect(),
+            updated,
+            moved,
+        }
+    }
+}
+
+fn check_index(index: usize, len: usize) {
+    assert!(index < len, "index (is {index}) should be < len (is {len})");
+}
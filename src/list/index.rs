@@ -0,0 +1,167 @@
+//! mod `index` - lookup of list entries by key.
+//!
+//! See [`ListIndex`] for more info.
+
+use super::{EntryWatchers, List, ListEntry, ListObservation, ListObserver};
+use crate::shared::Shared;
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Weak};
+
+type IndexKeyFn<T, K> = Box<dyn Send + FnMut(&T) -> K>;
+
+/// An index of the entries of a [`List`] by a key computed from their values.
+///
+/// The index is kept consistent as entries are added to or removed from the list, and as their
+/// values change, so looking up an entry by key does not need to scan (and lock) every entry of
+/// the list.
+///
+/// ```rust
+/// use dioxus_shareables::{shareable, List};
+///
+/// pub struct Row {
+///     id: usize,
+///     name: &'static str,
+/// }
+/// shareable!(Rows: List<Row> = [Row { id: 3, name: "three" }, Row { id: 5, name: "five" }].into_iter().collect());
+///
+/// let by_id = Rows.share().index_by(|r| r.id);
+/// let five = by_id.lookup(&5).unwrap();
+/// assert_eq!(five.share().read().name, "five");
+///
+/// Rows.share().write().push(Row { id: 8, name: "eight" });
+/// assert_eq!(by_id.lookup(&8).unwrap().share().read().name, "eight");
+///
+/// five.share().write().id = 6;
+/// assert!(by_id.lookup(&5).is_none());
+/// assert!(by_id.lookup(&6) == Some(five));
+///
+/// // The index follows the list even when it is replaced.
+/// *Rows.share().write() = [Row { id: 1, name: "one" }].into_iter().collect();
+/// assert!(by_id.lookup(&6).is_none());
+/// assert_eq!(by_id.lookup(&1).unwrap().share().read().name, "one");
+/// ```
+///
+/// Keys need not be unique: [`lookup`](Self::lookup) returns the entry which has had the key the
+/// longest, and [`lookup_all`](Self::lookup_all) returns all of them.
+pub struct ListIndex<T: 'static, K: 'static>(Arc<IndexInner<T, K>>);
+impl<T: 'static, K: 'static> Clone for ListIndex<T, K> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+struct IndexInner<T: 'static, K: 'static> {
+    me: Weak<IndexInner<T, K>>,
+    list: ListObservation<T>,
+    state: Mutex<IndexState<T, K>>,
+}
+struct IndexState<T, K> {
+    key: IndexKeyFn<T, K>,
+    index: FxHashMap<K, Vec<ListEntry<T>>>,
+    /// The current key of each entry, along with its watcher.
+    entries: EntryWatchers<T, K>,
+}
+impl<T, K: Eq + Hash> IndexState<T, K> {
+    fn unindex(&mut self, key: &K, entry: &ListEntry<T>) {
+        if let Some(bucket) = self.index.get_mut(key) {
+            bucket.retain(|l| l != entry);
+            if bucket.is_empty() {
+                self.index.remove(key);
+            }
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync, B: 'static> Shared<List<T>, B> {
+    /// Create an index of the entries of the list by `key`.
+    ///
+    /// See [`ListIndex`] for more info.
+    pub fn index_by<K, F>(&self, key: F) -> ListIndex<T, K>
+    where
+        K: 'static + Send + Sync + Eq + Hash + Clone,
+        F: 'static + Send + FnMut(&T) -> K,
+    {
+        let inner = Arc::new_cyclic(|me| IndexInner {
+            me: me.clone(),
+            list: ListObservation::new(&self.link),
+            state: Mutex::new(IndexState {
+                key: Box::new(key),
+                index: FxHashMap::default(),
+                entries: EntryWatchers::new(),
+            }),
+        });
+        let weak: Weak<IndexInner<T, K>> = Arc::downgrade(&inner);
+        inner.list.start(weak);
+        ListIndex(inner)
+    }
+}
+
+impl<T: 'static, K: 'static + Eq + Hash> ListIndex<T, K> {
+    /// Find an entry of the list with the given key.
+    pub fn lookup(&self, key: &K) -> Option<ListEntry<T>> {
+        self.0.state.lock().index.get(key)?.first().cloned()
+    }
+    /// Find all the entries of the list with the given key.
+    pub fn lookup_all(&self, key: &K) -> Vec<ListEntry<T>> {
+        self.0
+            .state
+            .lock()
+            .index
+            .get(key)
+            .cloned()
+            .unwrap_or_default()
+    }
+    /// Check whether any entry of the list has the given key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.0.state.lock().index.contains_key(key)
+    }
+    /// The number of distinct keys in the index.
+    pub fn len(&self) -> usize {
+        self.0.state.lock().index.len()
+    }
+    /// Check whether the index (and therefore the list) is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.state.lock().index.is_empty()
+    }
+}
+
+impl<T: 'static + Send + Sync, K: 'static + Send + Sync + Eq + Hash + Clone> IndexInner<T, K> {
+    /// Re-key a single entry of the list.
+    fn refresh(&self, entry: &ListEntry<T>) {
+        let mut state = self.state.lock();
+        let key = (state.key)(&entry.0.borrow());
+        let Some(prev) = state.entries.get_mut(entry) else {
+            return;
+        };
+        if *prev != key {
+            let prev = std::mem::replace(prev, key.clone());
+            state.unindex(&prev, entry);
+            state.index.entry(key).or_default().push(entry.clone());
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync, K: 'static + Send + Sync + Eq + Hash + Clone> ListObserver<T>
+    for IndexInner<T, K>
+{
+    fn added(&self, added: &[ListEntry<T>]) {
+        let mut state = self.state.lock();
+        for entry in added {
+            let key = (state.key)(&entry.0.borrow());
+            state
+                .entries
+                .watch_for(entry, key.clone(), &self.me, Self::refresh);
+            state.index.entry(key).or_default().push(entry.clone());
+        }
+    }
+    fn removed(&self, removed: &[ListEntry<T>]) {
+        let mut state = self.state.lock();
+        for entry in removed {
+            if let Some(key) = state.entries.unwatch(entry) {
+                state.unindex(&key, entry);
+            }
+        }
+    }
+}
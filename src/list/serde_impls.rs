@@ -0,0 +1,115 @@
+//! `serde` support (with the `serde` feature.)
+//!
+//! Lists are serialized as sequences of their values, and entries and shared values are
+//! serialized as snapshots of their current value.
+
+use super::{List, ListEntry};
+use crate::shared::{Link, Shared};
+use serde::de::{SeqAccess, Visitor};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::sync::Arc;
+
+impl<T: Serialize> Serialize for List<T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut seq = s.serialize_seq(Some(self.len()))?;
+        for l in &self.0 {
+            seq.serialize_element(&*l.0.borrow())?;
+        }
+        seq.end()
+    }
+}
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for List<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        Ok(Vec::<T>::deserialize(d)?.into_iter().collect())
+    }
+}
+impl<T> List<T> {
+    /// Deserialize a sequence of values into the list in place.
+    ///
+    /// Rather than replacing every entry, the values of existing entries are updated, new entries
+    /// are pushed for any extra values, and any extra entries are removed. Components which are
+    /// linked to the first entries of the list therefore stay linked to them, and are only updated
+    /// if their entry's value actually changed.
+    ///
+    /// If the deserializer fails partway through, the entries before the failure keep (and have
+    /// been notified of) their new values, and the rest of the list is left as it was.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// use dioxus_shareables::{shareable, List};
+    ///
+    /// shareable!(Numbers: List<usize> = [3, 5, 7].into_iter().collect());
+    ///
+    /// let saved = serde_json::to_string(&*Numbers.share().read()).unwrap();
+    /// assert_eq!(saved, "[3,5,7]");
+    ///
+    /// let first = Numbers.share().read().get(0).unwrap();
+    /// let mut de = serde_json::Deserializer::from_str("[4,6]");
+    /// Numbers.share().write().reconcile(&mut de).unwrap();
+    /// assert_eq!(*first.share().read(), 4); // The first entry was updated in place.
+    /// assert_eq!(Numbers.share().read().len(), 2);
+    ///
+    /// let mut de = serde_json::Deserializer::from_str("[8,\"x\"]");
+    /// assert!(Numbers.share().write().reconcile(&mut de).is_err());
+    /// assert_eq!(*first.share().read(), 8); // Entries before the error were still updated.
+    /// # assert_eq!(*Numbers.share().read().get(1).unwrap().share().read(), 6);
+    /// ```
+    pub fn reconcile<'de, D: Deserializer<'de>>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        T: Deserialize<'de> + PartialEq,
+    {
+        deserializer.deserialize_seq(ReconcileVisitor(self))
+    }
+}
+struct ReconcileVisitor<'a, T>(&'a mut List<T>);
+impl<'a, 'de, T: Deserialize<'de> + PartialEq> Visitor<'de> for ReconcileVisitor<'a, T> {
+    type Value = ();
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a sequence")
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut len = 0;
+        while let Some(l) = self.0 .0.get(len).cloned() {
+            let Some(t) = seq.next_element::<T>()? else {
+                break;
+            };
+            // Each value is deserialized whole before it is stored, so that an error leaves the
+            // entry as it was.
+            if *l.0.borrow() != t {
+                *l.0.borrow_mut() = t;
+                l.0.needs_update();
+            }
+            len += 1;
+        }
+        self.0.truncate(len);
+        let mut extra = Vec::new();
+        while let Some(t) = seq.next_element()? {
+            extra.push(t)
+        }
+        self.0.extend(extra);
+        Ok(())
+    }
+}
+
+impl<T: Serialize> Serialize for ListEntry<T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.0.borrow().serialize(s)
+    }
+}
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ListEntry<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        T::deserialize(d).map(ListEntry::new)
+    }
+}
+
+impl<T: Serialize, B> Serialize for Shared<T, B> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.link.borrow().serialize(s)
+    }
+}
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Shared<T, crate::W> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        T::deserialize(d).map(|t| Shared::from_link(Arc::new(Link::new(t))))
+    }
+}
@@ -0,0 +1,286 @@
+//! mod `slice` - borrowed views of the entries of lists.
+//!
+//! See [`ListSlice`] and [`ListSliceMut`] for more info.
+
+use super::{List, ListEntry};
+use std::ops::{
+    Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+};
+
+/// A borrowed view of some of the entries of a [`List`].
+///
+/// This is to a `List` what a slice is to a `Vec`: it is obtained with [`List::as_slice`] and
+/// provides the slice API, handing out [`ListEntry`]s rather than references to the values. To
+/// reorder part of a list, use [`ListSliceMut`].
+///
+/// ```rust
+/// use dioxus_shareables::{shareable, List};
+///
+/// shareable!(Numbers: List<usize> = [1, 2, 3, 4, 5].into_iter().collect());
+///
+/// let numbers = Numbers.share();
+/// let numbers = numbers.read();
+/// let value = |l: dioxus_shareables::ListEntry<usize>| *l.share().read();
+///
+/// let middle = numbers.as_slice().get(1..4).unwrap();
+/// assert_eq!(middle.iter().rev().map(value).collect::<Vec<_>>(), [4, 3, 2]);
+///
+/// let sums = numbers.as_slice().windows(2).map(|w| w.iter().map(value).sum::<usize>());
+/// assert_eq!(sums.collect::<Vec<_>>(), [3, 5, 7, 9]);
+///
+/// let (left, right) = numbers.as_slice().split_at(2);
+/// assert_eq!((left.len(), right.len()), (2, 3));
+/// assert!(right.first() == numbers.get(2));
+/// ```
+pub struct ListSlice<'a, T>(&'a [ListEntry<T>]);
+impl<'a, T> Clone for ListSlice<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'a, T> Copy for ListSlice<'a, T> {}
+
+/// A type which can be used to index a [`ListSlice`].
+///
+/// This is implemented for `usize`, which gives a [`ListEntry`], and for ranges of `usize`, which
+/// give a [`ListSlice`].
+pub trait ListSliceIndex<'a, T: 'a> {
+    type Output;
+    #[doc(hidden)]
+    fn get(self, entries: &'a [ListEntry<T>]) -> Option<Self::Output>;
+}
+impl<'a, T: 'a> ListSliceIndex<'a, T> for usize {
+    type Output = ListEntry<T>;
+    fn get(self, entries: &'a [ListEntry<T>]) -> Option<ListEntry<T>> {
+        entries.get(self).cloned()
+    }
+}
+macro_rules! impl_ListSliceIndex_for_ranges {
+    ($($R:ty),*) => {$(
+        impl<'a, T: 'a> ListSliceIndex<'a, T> for $R {
+            type Output = ListSlice<'a, T>;
+            fn get(self, entries: &'a [ListEntry<T>]) -> Option<ListSlice<'a, T>> {
+                entries.get(self).map(ListSlice)
+            }
+        }
+    )*};
+}
+impl_ListSliceIndex_for_ranges!(
+    Range<usize>,
+    RangeFrom<usize>,
+    RangeFull,
+    RangeInclusive<usize>,
+    RangeTo<usize>,
+    RangeToInclusive<usize>
+);
+
+#[allow(non_camel_case_types)]
+pub type slice_of<'a, T> = fn(&'a [ListEntry<T>]) -> ListSlice<'a, T>;
+pub type Windows<'a, T> = std::iter::Map<std::slice::Windows<'a, ListEntry<T>>, slice_of<'a, T>>;
+pub type Chunks<'a, T> = std::iter::Map<std::slice::Chunks<'a, ListEntry<T>>, slice_of<'a, T>>;
+pub type ChunksExact<'a, T> =
+    std::iter::Map<std::slice::ChunksExact<'a, ListEntry<T>>, slice_of<'a, T>>;
+pub type RChunks<'a, T> = std::iter::Map<std::slice::RChunks<'a, ListEntry<T>>, slice_of<'a, T>>;
+
+impl<T> List<T> {
+    /// Get a borrowed view of all the entries of the list.
+    pub fn as_slice(&self) -> ListSlice<'_, T> {
+        ListSlice(&self.0)
+    }
+    /// Get a mutable borrowed view of all the entries of the list.
+    pub fn as_mut_slice(&mut self) -> ListSliceMut<'_, T> {
+        ListSliceMut(&mut self.0)
+    }
+}
+
+impl<'a, T> ListSlice<'a, T> {
+    /// See [`[_]::binary_search_by`]
+    pub fn binary_search_by<F: FnMut(&T) -> std::cmp::Ordering>(
+        &self,
+        mut f: F,
+    ) -> Result<usize, usize> {
+        self.0.binary_search_by(|l| f(&l.0.borrow()))
+    }
+    /// See [`[_]::chunks`]
+    pub fn chunks(&self, chunk_size: usize) -> Chunks<'a, T> {
+        self.0.chunks(chunk_size).map(ListSlice)
+    }
+    /// See [`[_]::chunks_exact`]
+    pub fn chunks_exact(&self, chunk_size: usize) -> ChunksExact<'a, T> {
+        self.0.chunks_exact(chunk_size).map(ListSlice)
+    }
+    /// See [`[_]::contains`]
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.0.iter().any(|l| x == &*l.0.borrow())
+    }
+    /// See [`[_]::first`]
+    pub fn first(&self) -> Option<ListEntry<T>> {
+        self.0.first().cloned()
+    }
+    /// See [`[_]::get`]
+    pub fn get<I: ListSliceIndex<'a, T>>(&self, index: I) -> Option<I::Output> {
+        index.get(self.0)
+    }
+    /// See [`[_]::is_empty`]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// See [`[_]::iter`]
+    pub fn iter(&self) -> std::iter::Cloned<std::slice::Iter<'a, ListEntry<T>>> {
+        self.0.iter().cloned()
+    }
+    /// See [`[_]::last`]
+    pub fn last(&self) -> Option<ListEntry<T>> {
+        self.0.last().cloned()
+    }
+    /// See [`[_]::len`]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// See [`[_]::partition_point`]
+    pub fn partition_point<P: FnMut(&T) -> bool>(&self, mut pred: P) -> usize {
+        self.0.partition_point(|l| pred(&l.0.borrow()))
+    }
+    /// See [`[_]::rchunks`]
+    pub fn rchunks(&self, chunk_size: usize) -> RChunks<'a, T> {
+        self.0.rchunks(chunk_size).map(ListSlice)
+    }
+    /// See [`[_]::split_at`]
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        let (l, r) = self.0.split_at(mid);
+        (ListSlice(l), ListSlice(r))
+    }
+    /// See [`[_]::split_first`]
+    pub fn split_first(&self) -> Option<(ListEntry<T>, Self)> {
+        let (first, rest) = self.0.split_first()?;
+        Some((first.clone(), ListSlice(rest)))
+    }
+    /// See [`[_]::split_last`]
+    pub fn split_last(&self) -> Option<(ListEntry<T>, Self)> {
+        let (last, rest) = self.0.split_last()?;
+        Some((last.clone(), ListSlice(rest)))
+    }
+    /// See [`[_]::windows`]
+    pub fn windows(&self, size: usize) -> Windows<'a, T> {
+        self.0.windows(size).map(ListSlice)
+    }
+}
+impl<'a, T> IntoIterator for ListSlice<'a, T> {
+    type Item = ListEntry<T>;
+    type IntoIter = std::iter::Cloned<std::slice::Iter<'a, ListEntry<T>>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter().cloned()
+    }
+}
+
+/// A mutable borrowed view of some of the entries of a [`List`].
+///
+/// This is to a `List` what a mutable slice is to a `Vec`: it is obtained with
+/// [`List::as_mut_slice`], and can reorder the entries within a range of the list. Entries are
+/// moved, rather than their values, so components which hold an entry keep following it.
+///
+/// ```rust
+/// use dioxus_shareables::{shareable, List};
+///
+/// shareable!(Numbers: List<usize> = [1, 5, 3, 4, 2, 6].into_iter().collect());
+///
+/// let numbers = Numbers.share();
+/// let values = || numbers.read().iter().map(|l| *l.share().read()).collect::<Vec<_>>();
+/// let five = numbers.read().get(1).unwrap();
+///
+/// numbers.write().as_mut_slice().get_mut(1..5).unwrap().sort();
+/// assert_eq!(values(), [1, 2, 3, 4, 5, 6]);
+/// assert!(numbers.read().get(4).unwrap() == five); // The entry moved with its value.
+///
+/// let mut w = numbers.write();
+/// let mut all = w.as_mut_slice();
+/// let (mut left, mut right) = all.split_at_mut(3);
+/// left.reverse();
+/// right.rotate_left(1);
+/// drop(w);
+/// assert_eq!(values(), [3, 2, 1, 5, 6, 4]);
+/// ```
+pub struct ListSliceMut<'a, T>(&'a mut [ListEntry<T>]);
+
+impl<'a, T> ListSliceMut<'a, T> {
+    /// Get a borrowed view of the entries.
+    pub fn as_slice(&self) -> ListSlice<'_, T> {
+        ListSlice(self.0)
+    }
+    /// See [`[_]::get_mut`]
+    ///
+    /// Unlike `[_]::get_mut`, this only takes ranges: to change the value of a single entry, use
+    /// [`ListEntry::share`].
+    pub fn get_mut<R: RangeBounds<usize>>(&mut self, range: R) -> Option<ListSliceMut<'_, T>> {
+        let range: (Bound<usize>, Bound<usize>) =
+            (range.start_bound().cloned(), range.end_bound().cloned());
+        self.0.get_mut(range).map(ListSliceMut)
+    }
+    /// See [`[_]::is_empty`]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// See [`[_]::len`]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// See [`[_]::reverse`]
+    pub fn reverse(&mut self) {
+        self.0.reverse()
+    }
+    /// See [`[_]::rotate_left`]
+    pub fn rotate_left(&mut self, mid: usize) {
+        self.0.rotate_left(mid)
+    }
+    /// See [`[_]::rotate_right`]
+    pub fn rotate_right(&mut self, mid: usize) {
+        self.0.rotate_right(mid)
+    }
+    /// See [`[_]::sort`]
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(Ord::cmp)
+    }
+    /// See [`[_]::sort_by`]
+    pub fn sort_by<F: FnMut(&T, &T) -> std::cmp::Ordering>(&mut self, mut f: F) {
+        self.0.sort_by(|a, b| f(&a.0.borrow(), &b.0.borrow()))
+    }
+    /// See [`[_]::sort_by_cached_key`]
+    pub fn sort_by_cached_key<U: Ord, F: FnMut(&T) -> U>(&mut self, mut f: F) {
+        self.0.sort_by_cached_key(|a| f(&a.0.borrow()))
+    }
+    /// See [`[_]::sort_by_key`]
+    pub fn sort_by_key<U: Ord, F: FnMut(&T) -> U>(&mut self, mut f: F) {
+        self.0.sort_by_key(|a| f(&a.0.borrow()))
+    }
+    /// See [`[_]::sort_unstable`]
+    pub fn sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_unstable_by(Ord::cmp)
+    }
+    /// See [`[_]::sort_unstable_by`]
+    pub fn sort_unstable_by<F: FnMut(&T, &T) -> std::cmp::Ordering>(&mut self, mut f: F) {
+        self.0
+            .sort_unstable_by(|a, b| f(&a.0.borrow(), &b.0.borrow()))
+    }
+    /// See [`[_]::sort_unstable_by_key`]
+    pub fn sort_unstable_by_key<U: Ord, F: FnMut(&T) -> U>(&mut self, mut f: F) {
+        self.0.sort_unstable_by_key(|a| f(&a.0.borrow()))
+    }
+    /// See [`[_]::split_at_mut`]
+    pub fn split_at_mut(&mut self, mid: usize) -> (ListSliceMut<'_, T>, ListSliceMut<'_, T>) {
+        let (l, r) = self.0.split_at_mut(mid);
+        (ListSliceMut(l), ListSliceMut(r))
+    }
+    /// See [`[_]::swap`]
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.0.swap(a, b)
+    }
+}
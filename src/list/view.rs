@@ -0,0 +1,210 @@
+//! mod `view` - live views of lists.
+//!
+//! See [`ListView`] for more info.
+
+use super::{EntryWatchers, List, ListEntry, ListObservation, ListObserver};
+use crate::shared::{Link, ReadOnly, Shareable, Shared};
+use parking_lot::Mutex;
+use std::cmp::Ordering;
+use std::sync::{Arc, Weak};
+
+type ViewFilter<T> = Box<dyn Send + FnMut(&T) -> bool>;
+type ViewSort<T> = Box<dyn Send + FnMut(&T, &T) -> Ordering>;
+
+/// A filtered and sorted view of a [`List`].
+///
+/// The view holds the entries of the list which pass its filter, ordered by its comparison
+/// function. The entries are the same [`ListEntry`]s as in the original list, so components
+/// which are passed an entry from the view can use it exactly as they would an entry from the
+/// list.
+///
+/// The view is updated incrementally: when entries are added to or removed from the list, or when
+/// the value of an entry changes, only that entry is re-filtered and re-positioned. Components
+/// which [`use_rw`](Self::use_rw) the view are updated only when the membership or the order of
+/// the view changes.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// use dioxus_shareables::{shareable, List, ListEntry, ListView};
+///
+/// shareable!(Numbers: List<usize> = [3, 8, 5, 12, 7].into_iter().collect());
+///
+/// #[allow(non_snake_case)]
+/// fn SmallNumbers(cx: Scope) -> Element {
+///     // The view must be kept alive for as long as it is in use, so we store it in a hook.
+///     let view = cx.use_hook(|| Numbers.share().view(|n| *n < 10, Ord::cmp));
+///     let nums = view.use_rw(&cx); // This component is updated when an entry enters, leaves or
+///                                  // moves within the view.
+///     cx.render(rsx! {
+///         ul {
+///             nums.read().iter().map(|n| rsx! { ListItem { num: n.clone() } })
+///         }
+///     })
+/// }
+///
+/// #[allow(non_snake_case)]
+/// #[inline_props]
+/// fn ListItem(cx: Scope, num: ListEntry<usize>) -> Element {
+///     let num = num.use_rw(&cx);
+///     let w = num.clone();
+///     let num = num.read();
+///     cx.render(rsx! {
+///         li { "{num}", button { onclick: move |_| *w.write() += 1, "+" } }
+///     })
+/// }
+///
+/// let numbers = Numbers.share();
+/// let view = numbers.view(|n| *n < 10, Ord::cmp);
+/// let values = |v: &ListView<usize>| v.share().read().iter().map(|n| *n.share().read()).collect::<Vec<_>>();
+/// assert_eq!(values(&view), [3, 5, 7, 8]);
+///
+/// numbers.write().push(4);
+/// assert_eq!(values(&view), [3, 4, 5, 7, 8]);
+///
+/// *numbers.read().get(1).unwrap().share().write() = 2; // 8 becomes 2
+/// assert_eq!(values(&view), [2, 3, 4, 5, 7]);
+///
+/// *numbers.read().get(0).unwrap().share().write() = 30; // 3 leaves the view
+/// assert_eq!(values(&view), [2, 4, 5, 7]);
+///
+/// // The view is updated once no write guards are held, so entries can be written together.
+/// let (e1, e2) = (numbers.read().get(1).unwrap().share(), numbers.read().get(2).unwrap().share());
+/// let (mut a, mut b) = (e1.write(), e2.write());
+/// (*a, *b) = (*a + 1, *b + 1);
+/// drop((a, b));
+/// assert_eq!(values(&view), [3, 4, 6, 7]);
+/// ```
+pub struct ListView<T: 'static>(Arc<ViewInner<T>>);
+impl<T: 'static> Clone for ListView<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+struct ViewInner<T: 'static> {
+    me: Weak<ViewInner<T>>,
+    list: ListObservation<T>,
+    link: Arc<Link<Vec<ListEntry<T>>>>,
+    state: Mutex<ViewState<T>>,
+}
+struct ViewState<T> {
+    filter: ViewFilter<T>,
+    sort: ViewSort<T>,
+    entries: EntryWatchers<T>,
+}
+
+impl<T: 'static + Send + Sync, B: 'static> Shared<List<T>, B> {
+    /// Create a live view of the entries of the list which pass `filter`, sorted according to
+    /// `sort`.
+    ///
+    /// Entries which compare equal keep the order in which they entered the view.
+    ///
+    /// See [`ListView`] for more info.
+    pub fn view<F, S>(&self, filter: F, sort: S) -> ListView<T>
+    where
+        F: 'static + Send + FnMut(&T) -> bool,
+        S: 'static + Send + FnMut(&T, &T) -> Ordering,
+    {
+        let inner = Arc::new_cyclic(|me| ViewInner {
+            me: me.clone(),
+            list: ListObservation::new(&self.link),
+            link: Arc::new(Link::new(Vec::new())),
+            state: Mutex::new(ViewState {
+                filter: Box::new(filter),
+                sort: Box::new(sort),
+                entries: EntryWatchers::new(),
+            }),
+        });
+        let weak: Weak<ViewInner<T>> = Arc::downgrade(&inner);
+        inner.list.start(weak);
+        ListView(inner)
+    }
+}
+
+impl<T: 'static> ListView<T> {
+    /// Get a read pointer to the contents of the view.
+    ///
+    /// Scope `cx` will be registered as needing update every time entries enter, leave or move
+    /// within the view.
+    pub fn use_rw<'a, P>(
+        &self,
+        cx: &dioxus_core::Scope<'a, P>,
+    ) -> &'a ReadOnly<Vec<ListEntry<T>>, crate::RW> {
+        let mut opt = Shareable(Some(self.0.link.clone()));
+        ReadOnly::new(Shared::init(cx, &mut opt, || unreachable!(), crate::RW))
+    }
+    /// Get a read pointer to the contents of the view without registering a hook.
+    pub fn share(&self) -> ReadOnly<Vec<ListEntry<T>>, crate::W> {
+        ReadOnly::from_link(self.0.link.clone())
+    }
+}
+
+impl<T: 'static + Send + Sync> ViewInner<T> {
+    /// Re-filter and re-position a single entry of the original list.
+    fn refresh(&self, entry: &ListEntry<T>) {
+        let mut state = self.state.lock();
+        let ViewState {
+            filter,
+            sort,
+            entries,
+        } = &mut *state;
+        // The entry may have been removed from the list before a deferred watcher ran.
+        if !entries.contains(entry) {
+            return;
+        }
+        let keep = filter(&entry.0.borrow());
+        let mut view = self.link.borrow_mut();
+        let prev = view.iter().position(|l| l == entry);
+        let changed = match (prev, keep) {
+            (None, false) => false,
+            (Some(i), false) => {
+                view.remove(i);
+                true
+            }
+            (prev, true) => {
+                if let Some(i) = prev {
+                    view.remove(i);
+                }
+                let value = entry.0.borrow();
+                let i = view.partition_point(|l| sort(&l.0.borrow(), &value) != Ordering::Greater);
+                drop(value);
+                view.insert(i, entry.clone());
+                prev != Some(i)
+            }
+        };
+        drop(view);
+        drop(state);
+        if changed {
+            self.link.needs_update();
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync> ListObserver<T> for ViewInner<T> {
+    fn added(&self, entries: &[ListEntry<T>]) {
+        for entry in entries {
+            self.state
+                .lock()
+                .entries
+                .watch_for(entry, (), &self.me, Self::refresh);
+            self.refresh(entry);
+        }
+    }
+    fn removed(&self, entries: &[ListEntry<T>]) {
+        let mut changed = false;
+        {
+            let mut state = self.state.lock();
+            let mut view = self.link.borrow_mut();
+            for entry in entries {
+                state.entries.unwatch(entry);
+                if let Some(i) = view.iter().position(|l| l == entry) {
+                    view.remove(i);
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            self.link.needs_update();
+        }
+    }
+}
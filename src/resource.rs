@@ -0,0 +1,257 @@
+//! mod `resource` - shared values which are loaded asynchronously.
+//!
+//! See [`shareable_resource!`](crate::shareable_resource) for more info.
+
+use crate::Shared;
+use parking_lot::Mutex;
+use std::future::Future;
+
+/// The default error type of a [`shareable_resource!`](crate::shareable_resource).
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// The state of a value which is loaded asynchronously.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Resource<T, E = Error> {
+    /// The value has not been loaded yet.
+    Loading,
+    /// The value was loaded.
+    Ready(T),
+    /// Loading the value failed.
+    Failed(E),
+}
+impl<T, E> Resource<T, E> {
+    /// Check whether the value has not been loaded yet.
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Self::Loading)
+    }
+    /// Get the value, if it was loaded.
+    pub fn ready(&self) -> Option<&T> {
+        match self {
+            Self::Ready(t) => Some(t),
+            _ => None,
+        }
+    }
+    /// Get the error, if loading the value failed.
+    pub fn failed(&self) -> Option<&E> {
+        match self {
+            Self::Failed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+impl<T, E> From<Result<T, E>> for Resource<T, E> {
+    fn from(r: Result<T, E>) -> Self {
+        match r {
+            Ok(t) => Self::Ready(t),
+            Err(e) => Self::Failed(e),
+        }
+    }
+}
+
+/// Keeps track of when a resource needs to be (re)loaded, so that it is only loaded once at a
+/// time.
+#[doc(hidden)]
+pub struct Fetcher(Mutex<FetchState>);
+struct FetchState {
+    stale: bool,
+    in_flight: bool,
+}
+impl Fetcher {
+    pub const fn new() -> Self {
+        Self(Mutex::new(FetchState {
+            stale: true,
+            in_flight: false,
+        }))
+    }
+    /// Start loading the resource in scope `cx` if it is stale and not already loading.
+    pub fn poll<P, T, E, Fut, F>(
+        &'static self,
+        cx: &dioxus_core::Scope<P>,
+        value: Shared<Resource<T, E>, crate::W>,
+        f: F,
+    ) where
+        T: 'static,
+        E: 'static,
+        Fut: 'static + Future<Output = Result<T, E>>,
+        F: FnOnce() -> Fut,
+    {
+        {
+            let mut state = self.0.lock();
+            if !state.stale || state.in_flight {
+                return;
+            }
+            state.stale = false;
+            state.in_flight = true;
+        }
+        let mut flight = InFlight {
+            fetcher: self,
+            value,
+            done: false,
+        };
+        let fut = f();
+        cx.push_future(async move {
+            let r = fut.await;
+            flight.done = true;
+            let value = flight.value.clone();
+            drop(flight);
+            *value.write() = r.into();
+        });
+    }
+    /// Mark the resource as stale, so that it will be reloaded the next time it is used.
+    pub fn refresh<T, E>(&self, value: &Shared<Resource<T, E>, crate::W>) {
+        self.0.lock().stale = true;
+        value.needs_update();
+    }
+    pub fn is_fetching(&self) -> bool {
+        self.0.lock().in_flight
+    }
+}
+impl Default for Fetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Marks a fetch as finished when it is dropped, including when the task running it is cancelled
+/// (in which case the resource is marked stale again, and the components using it are updated so
+/// that one of them starts loading it anew.)
+struct InFlight<T: 'static, E: 'static> {
+    fetcher: &'static Fetcher,
+    value: Shared<Resource<T, E>, crate::W>,
+    done: bool,
+}
+impl<T: 'static, E: 'static> Drop for InFlight<T, E> {
+    fn drop(&mut self) {
+        {
+            let mut state = self.fetcher.0.lock();
+            state.in_flight = false;
+            if self.done {
+                return;
+            }
+            state.stale = true;
+        }
+        self.value.needs_update();
+    }
+}
+
+/// Create a shared value which is loaded asynchronously.
+///
+/// The value of the shareable is a [`Resource`], which is [`Loading`](Resource::Loading) until
+/// the future given in the declaration completes, and then [`Ready`](Resource::Ready) or
+/// [`Failed`](Resource::Failed) depending on its result. The future is started the first time
+/// the resource is used with `use_rw` (in the scope of the component which uses it), and it is
+/// only started once, no matter how many components use the resource.
+///
+/// Calling `refresh()` loads the value again. The previous value stays visible until the new one
+/// is ready, i.e. the resource is stale-while-revalidate.
+///
+/// The future must return a `Result<T, E>`; by default `E` is [`Error`], but another error type
+/// may be given after the value type:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// use dioxus_shareables::{resource::Resource, shareable_resource};
+///
+/// #[derive(Clone, PartialEq, Debug)]
+/// pub struct UserData {
+///     name: String,
+/// }
+/// async fn fetch_user() -> Result<UserData, String> {
+///     Ok(UserData { name: "alice".into() })
+/// }
+///
+/// shareable_resource!(pub User: UserData, String = async { fetch_user().await });
+///
+/// #[allow(non_snake_case)]
+/// fn Greeting(cx: Scope) -> Element {
+///     let user = User.use_rw(&cx); // This component is updated when the user is loaded.
+///     cx.render(match &*user.read() {
+///         Resource::Loading => rsx! { "Loading..." },
+///         Resource::Ready(user) => rsx! { "Hello, {user.name}!" },
+///         Resource::Failed(e) => rsx! { "Error: {e}" },
+///     })
+/// }
+///
+/// let mut dom = VirtualDom::new(Greeting);
+/// let _ = dom.rebuild();
+/// assert!(User.share().read().is_loading());
+/// futures::executor::block_on(dom.wait_for_work());
+/// assert_eq!(User.share().read().ready().unwrap().name, "alice");
+/// ```
+///
+/// If the component which started loading the resource is unmounted before the value is ready,
+/// loading starts again in another component which uses the resource:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// use dioxus_shareables::{shareable, shareable_resource};
+///
+/// shareable!(ShowDetails: bool = true);
+/// shareable_resource!(Slow: u32 = std::future::pending());
+///
+/// #[allow(non_snake_case)]
+/// fn App(cx: Scope) -> Element {
+///     let show = *ShowDetails.use_rw(&cx).read();
+///     cx.render(rsx! {
+///         show.then(|| rsx! { Details {} }),
+///         Summary {}
+///     })
+/// }
+/// #[allow(non_snake_case)]
+/// fn Details(cx: Scope) -> Element {
+///     let _slow = Slow.use_rw(&cx);
+///     cx.render(rsx! { "Details" })
+/// }
+/// #[allow(non_snake_case)]
+/// fn Summary(cx: Scope) -> Element {
+///     let _slow = Slow.use_rw(&cx);
+///     cx.render(rsx! { "Summary" })
+/// }
+///
+/// let mut dom = VirtualDom::new(App);
+/// let _ = dom.rebuild();
+/// assert!(Slow.is_fetching());
+/// *ShowDetails.share().write() = false; // Unmounting `Details` cancels its fetch...
+/// for _ in 0..2 {
+///     dom.process_events();
+///     let _ = dom.render_immediate();
+/// }
+/// assert!(Slow.is_fetching()); // ... so `Summary` starts another one.
+/// ```
+#[macro_export]
+macro_rules! shareable_resource {
+    ($(#[$meta:meta])*$vis:vis $IDENT:ident: $T:ty $(, $E:ty)? = $($fut:tt)*) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy)]
+        $vis struct $IDENT;
+        const _: () = {
+            $crate::shareable!(Value: $crate::resource::Resource<$T $(, $E)?> = $crate::resource::Resource::Loading);
+            static FETCHER: $crate::resource::Fetcher = $crate::resource::Fetcher::new();
+            impl $IDENT {
+                /// Obtain a RW pointer to the resource, and start loading it if needed.
+                ///
+                /// `cx` will be marked as needing update each time the resource changes.
+                pub fn use_rw<'a, P>(self, cx: &$crate::reexported::Scope<'a, P>) -> &'a mut $crate::Shared<$crate::resource::Resource<$T $(, $E)?>, $crate::RW> {
+                    let r = Value.use_rw(cx);
+                    FETCHER.poll(cx, Value.share(), || $($fut)*);
+                    r
+                }
+                /// Get a pointer to the resource, but don't call 'use_hook' or start loading it.
+                pub fn share(self) -> $crate::Shared<$crate::resource::Resource<$T $(, $E)?>, $crate::W> {
+                    Value.share()
+                }
+                /// Load the resource again.
+                ///
+                /// The current value is kept until the new value is ready. Loading starts the next
+                /// time a component which uses the resource is rendered.
+                pub fn refresh(self) {
+                    FETCHER.refresh(&Value.share())
+                }
+                /// Check whether the resource is currently being loaded.
+                pub fn is_fetching(self) -> bool {
+                    FETCHER.is_fetching()
+                }
+            }
+        };
+    };
+}
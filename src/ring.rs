@@ -0,0 +1,207 @@
+//! mod `ring` - bounded lists of shared values.
+//!
+//! See [`Ring`] for more info.
+
+use crate::list::{detach, ListEntry};
+use crate::shared::{Link, Shareable, Shared};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// A bounded list of shareable values, which drops its oldest entries to make room for new ones.
+///
+/// A `Ring` uses the same entries as a [`List`](crate::List), so components which are passed an
+/// entry are updated only when that entry changes. Unlike a `List`, evicting the oldest entry
+/// doesn't shift the position of every other entry.
+///
+/// Components which only need to know when entries are appended (e.g. to scroll a log to the
+/// bottom, or to count unread lines) can use [`use_appended`](Self::use_appended), which is not
+/// updated when entries are evicted or when their values change.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// use dioxus_shareables::{shareable, ListEntry, Ring};
+///
+/// shareable!(Log: Ring<String> = Ring::new(3));
+///
+/// #[allow(non_snake_case)]
+/// fn Console(cx: Scope) -> Element {
+///     let log = Log.use_rw(&cx); // This component is updated when lines are added or evicted.
+///     cx.render(rsx! {
+///         ul {
+///             log.read().iter().map(|line| rsx! { LogLine { line: line } })
+///         }
+///     })
+/// }
+///
+/// #[allow(non_snake_case)]
+/// #[inline_props]
+/// fn LogLine(cx: Scope, line: ListEntry<String>) -> Element {
+///     let line = line.use_rw(&cx); // This component is updated when this line changes.
+///     cx.render(rsx! { li { "{line.read()}" } })
+/// }
+///
+/// #[allow(non_snake_case)]
+/// fn LineCount(cx: Scope) -> Element {
+///     let count = Log.share().read().use_appended(&cx).clone(); // Only updated on appends.
+///     cx.render(rsx! { "{count.read()} lines logged" })
+/// }
+///
+/// let log = Log.share();
+/// log.write().push_back("line 0".into());
+/// let first = log.read().front().unwrap();
+/// for i in 1..5 {
+///     log.write().push_back(format!("line {i}"));
+/// }
+/// let lines = log.read().iter().map(|l| l.share().read().clone()).collect::<Vec<_>>();
+/// assert_eq!(lines, ["line 2", "line 3", "line 4"]);
+/// assert_eq!(log.read().appended(), 5);
+/// assert!(first.share().is_detached()); // Evicted entries are detached.
+/// # log.write().extend(["line 5".to_string(), "line 6".to_string()]);
+/// # assert_eq!(log.read().appended(), 7);
+/// # assert_eq!(*log.read().front().unwrap().share().read(), "line 4");
+/// ```
+pub struct Ring<T: 'static> {
+    entries: VecDeque<ListEntry<T>>,
+    capacity: usize,
+    appended: Arc<Link<u64>>,
+}
+
+impl<T: 'static> Ring<T> {
+    /// Create an empty ring which holds at most `capacity` entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "ring capacity must be non-zero");
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            appended: Arc::new(Link::new(0)),
+        }
+    }
+    /// The maximum number of entries in the ring.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    /// Change the maximum number of entries in the ring, evicting the oldest entries if there are
+    /// too many.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        assert!(capacity > 0, "ring capacity must be non-zero");
+        self.capacity = capacity;
+        let excess = self.entries.len().saturating_sub(capacity);
+        detach(self.entries.drain(..excess))
+    }
+    /// The number of entries in the ring.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Check whether the ring is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// Check whether the next call to [`push_back`](Self::push_back) will evict an entry.
+    pub fn is_full(&self) -> bool {
+        self.entries.len() == self.capacity
+    }
+    /// The total number of entries which have ever been appended to the ring.
+    pub fn appended(&self) -> u64 {
+        *self.appended.borrow()
+    }
+    /// Get a read-write pointer to the number of entries which have been appended to the ring.
+    ///
+    /// Scope `cx` will be registered as needing update every time an entry is appended, but not
+    /// when entries are evicted or removed, or when their values change.
+    pub fn use_appended<'a, P>(
+        &self,
+        cx: &dioxus_core::Scope<'a, P>,
+    ) -> &'a mut Shared<u64, crate::RW> {
+        let mut opt = Shareable(Some(self.appended.clone()));
+        Shared::init(cx, &mut opt, || unreachable!(), crate::RW)
+    }
+    /// Append a value to the ring.
+    ///
+    /// If the ring is full, its oldest entry is evicted and returned.
+    pub fn push_back(&mut self, value: T) -> Option<Shared<T, crate::W>> {
+        let evicted = self.append(value);
+        detach(&evicted);
+        self.count_appended(1);
+        evicted.map(|l| l.share())
+    }
+    /// Append a value without notifying anyone, returning the evicted entry if there is one.
+    fn append(&mut self, value: T) -> Option<ListEntry<T>> {
+        let evicted = if self.is_full() {
+            self.entries.pop_front()
+        } else {
+            None
+        };
+        self.entries.push_back(ListEntry::new(value));
+        evicted
+    }
+    fn count_appended(&self, n: u64) {
+        if n > 0 {
+            *self.appended.borrow_mut() += n;
+            self.appended.needs_update();
+        }
+    }
+    /// Remove the oldest entry of the ring.
+    pub fn pop_front(&mut self) -> Option<Shared<T, crate::W>> {
+        let l = self.entries.pop_front()?;
+        detach(std::slice::from_ref(&l));
+        Some(l.share())
+    }
+    /// Remove the newest entry of the ring.
+    pub fn pop_back(&mut self) -> Option<Shared<T, crate::W>> {
+        let l = self.entries.pop_back()?;
+        detach(std::slice::from_ref(&l));
+        Some(l.share())
+    }
+    /// Remove every entry of the ring.
+    pub fn clear(&mut self) {
+        detach(self.entries.drain(..))
+    }
+    /// Get the entry at `index`, counting from the oldest entry.
+    pub fn get(&self, index: usize) -> Option<ListEntry<T>> {
+        self.entries.get(index).cloned()
+    }
+    /// Get the oldest entry of the ring.
+    pub fn front(&self) -> Option<ListEntry<T>> {
+        self.entries.front().cloned()
+    }
+    /// Get the newest entry of the ring.
+    pub fn back(&self) -> Option<ListEntry<T>> {
+        self.entries.back().cloned()
+    }
+    /// Iterate over the entries of the ring, from oldest to newest.
+    pub fn iter(&self) -> <&Self as IntoIterator>::IntoIter {
+        self.into_iter()
+    }
+}
+impl<T: 'static> Drop for Ring<T> {
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+impl<'a, T> IntoIterator for &'a Ring<T> {
+    type Item = ListEntry<T>;
+    type IntoIter = std::iter::Cloned<std::collections::vec_deque::Iter<'a, ListEntry<T>>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter().cloned()
+    }
+}
+impl<T: 'static> Extend<T> for Ring<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut evicted = Vec::new();
+        let mut n = 0;
+        for t in iter {
+            evicted.extend(self.append(t));
+            n += 1;
+        }
+        detach(&evicted);
+        self.count_appended(n)
+    }
+}
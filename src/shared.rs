@@ -38,50 +38,222 @@
 //! ```
 
 use parking_lot::{
-    MappedRwLockReadGuard, MappedRwLockWriteGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    MappedRwLockReadGuard, MappedRwLockWriteGuard, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard,
 };
 use rustc_hash::FxHashMap;
-use std::sync::Arc;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
 
-type LinkUpdateMap = FxHashMap<usize, (usize, Arc<dyn Send + Sync + Fn()>)>;
+mod optimistic;
+pub use optimistic::Optimistic;
+mod lock;
+#[doc(hidden)]
+pub use lock::{Links, Locked};
+pub use lock::{LockedField, LockedMut, LockedRef};
+mod registry;
+#[doc(hidden)]
+pub use registry::Registry;
+
+/// Listeners by scope id: the number of hooks holding the listener, whether the scope has
+/// subscribed for a single update, and the callback which marks the scope as needing update.
+type LinkUpdateMap = FxHashMap<usize, (usize, bool, Arc<dyn Send + Sync + Fn()>)>;
+type LinkWatchMap = FxHashMap<usize, Arc<dyn Send + Sync + Fn()>>;
+/// The callbacks to run after one or more links have changed: one per listening scope, and one per
+/// watcher, however many of the links they listen to.
+#[derive(Default)]
+pub(crate) struct LinkUpdates {
+    scopes: FxHashMap<usize, Arc<dyn Send + Sync + Fn()>>,
+    watchers: LinkWatchMap,
+}
+impl LinkUpdates {
+    pub(crate) fn run(self) {
+        for u in self.scopes.into_values() {
+            u()
+        }
+        let watchers = DEFERRED.with(|d| {
+            let mut d = d.borrow_mut();
+            if d.0 == 0 {
+                Some(self.watchers)
+            } else {
+                d.1.extend(self.watchers);
+                None
+            }
+        });
+        for w in watchers.into_iter().flat_map(FxHashMap::into_values) {
+            w()
+        }
+    }
+}
+
+thread_local! {
+    /// The number of [`DeferWatchers`] held by this thread, and the watchers which were triggered
+    /// while any of them was held.
+    static DEFERRED: RefCell<(usize, LinkWatchMap)> = RefCell::new((0, FxHashMap::default()));
+}
+/// Defers the watchers which are triggered on this thread for as long as it is held.
+///
+/// This is held along with every write guard (and every set of [`Locked`] links), so that a
+/// watcher, which may read other values (e.g. the other entries of a
+/// [`ListView`](crate::list::ListView)), never runs while the thread might still hold one of them.
+/// Deferred watchers are run once the thread holds no guards, each of them only once.
+///
+/// The count is kept per thread, so this must be dropped on the thread which created it: it isn't
+/// `Send`, and neither is anything which holds it.
+pub(crate) struct DeferWatchers(std::marker::PhantomData<*const ()>);
+impl DeferWatchers {
+    pub(crate) fn new() -> Self {
+        DEFERRED.with(|d| d.borrow_mut().0 += 1);
+        Self(std::marker::PhantomData)
+    }
+}
+impl Drop for DeferWatchers {
+    fn drop(&mut self) {
+        let last = DEFERRED.with(|d| {
+            let mut d = d.borrow_mut();
+            d.0 -= 1;
+            d.0 == 0
+        });
+        if last {
+            // Watchers may write to other values in turn, which defers their own watchers again.
+            while let Some(watchers) = DEFERRED.with(|d| {
+                let mut d = d.borrow_mut();
+                (d.0 == 0 && !d.1.is_empty()).then(|| std::mem::take(&mut d.1))
+            }) {
+                for w in watchers.into_values() {
+                    w()
+                }
+            }
+        }
+    }
+}
 /// The actual shared data.
-pub(crate) struct Link<T>(RwLock<(T, LinkUpdateMap)>);
+///
+/// Besides the scopes which listen to a link, a link may have watchers: callbacks which are run
+/// after the value has changed, and which are used to keep derived data (e.g.
+/// [`ListView`](crate::list::ListView)) up to date. Watchers are not run while the thread holds a
+/// write guard (see [`DeferWatchers`].)
+///
+/// A link which belonged to a [`List`](crate::List) is marked as detached once it is removed.
+///
+/// While [optimistic updates](Shared::optimistic) are pending, the link also keeps the value they
+/// were applied to.
+pub(crate) struct Link<T> {
+    value: RwLock<T>,
+    /// The scopes which listen to the link. These are kept apart from the value so that a scope
+    /// can be notified (or start listening) while the value is borrowed.
+    listeners: Mutex<LinkUpdateMap>,
+    watchers: RwLock<LinkWatchMap>,
+    detached: AtomicBool,
+    optimistic: Mutex<Option<optimistic::Layers<T>>>,
+}
+pub(crate) fn next_watcher_id() -> usize {
+    static NEXT_WATCHER: AtomicUsize = AtomicUsize::new(0);
+    NEXT_WATCHER.fetch_add(1, Ordering::Relaxed)
+}
+/// Run the watcher `w` (with id `id`) as if one of the links it watches had changed: now, or once
+/// the thread holds no write guards.
+pub(crate) fn run_watcher(id: usize, w: Arc<dyn Send + Sync + Fn()>) {
+    let mut updates = LinkUpdates::default();
+    updates.watchers.insert(id, w);
+    updates.run()
+}
 impl<T> Link<T> {
     pub(crate) fn new(t: T) -> Self {
-        Self(RwLock::new((t, FxHashMap::default())))
+        Self {
+            value: RwLock::new(t),
+            listeners: Mutex::new(FxHashMap::default()),
+            watchers: RwLock::new(FxHashMap::default()),
+            detached: AtomicBool::new(false),
+            optimistic: Mutex::new(None),
+        }
+    }
+    /// Mark the link as detached and notify its listeners.
+    pub(crate) fn detach(&self) {
+        if !self.detached.swap(true, Ordering::Relaxed) {
+            self.needs_update()
+        }
+    }
+    pub(crate) fn is_detached(&self) -> bool {
+        self.detached.load(Ordering::Relaxed)
     }
     pub(crate) fn add_listener<F: FnOnce() -> Arc<dyn Send + Sync + Fn()>>(&self, id: usize, f: F) {
-        self.0.write().1.entry(id).or_insert_with(|| (0, f())).0 += 1;
+        self.listeners
+            .lock()
+            .entry(id)
+            .or_insert_with(|| (0, false, f()))
+            .0 += 1;
     }
     pub(crate) fn drop_listener(&self, id: usize) {
-        let mut p = self.0.write();
-        let c = if let Some((c, _)) = p.1.get_mut(&id) {
+        let mut p = self.listeners.lock();
+        let remove = if let Some((c, once, _)) = p.get_mut(&id) {
             *c -= 1;
-            *c
+            *c == 0 && !*once
         } else {
-            1
+            false
         };
-        if c == 0 {
-            p.1.remove(&id);
+        if remove {
+            p.remove(&id);
         }
     }
+    /// Add a listener which is only called the next time the link is updated.
+    pub(crate) fn add_listener_once<F: FnOnce() -> Arc<dyn Send + Sync + Fn()>>(
+        &self,
+        id: usize,
+        f: F,
+    ) {
+        self.listeners
+            .lock()
+            .entry(id)
+            .or_insert_with(|| (0, false, f()))
+            .1 = true;
+    }
+    /// Add a watcher to the link, returning an id which can be passed to
+    /// [`drop_watcher`](Self::drop_watcher).
+    pub(crate) fn add_watcher(&self, f: Arc<dyn Send + Sync + Fn()>) -> usize {
+        let id = next_watcher_id();
+        self.add_watcher_as(id, f);
+        id
+    }
+    /// Add a watcher with a given id. A watcher which is added to several links with the same id
+    /// runs once when they are updated together.
+    pub(crate) fn add_watcher_as(&self, id: usize, f: Arc<dyn Send + Sync + Fn()>) {
+        self.watchers.write().insert(id, f);
+    }
+    pub(crate) fn drop_watcher(&self, id: usize) {
+        self.watchers.write().remove(&id);
+    }
     pub(crate) fn needs_update(&self) {
-        for (_id, (_, u)) in self.0.read().1.iter().filter(|&(_, &(ct, _))| ct > 0) {
-            u()
-        }
+        // Callbacks are collected first so that none of them runs while the listeners are locked.
+        let mut updates = LinkUpdates::default();
+        self.collect_updates(&mut updates);
+        updates.run();
     }
-    pub(crate) fn borrow(&self) -> MappedRwLockReadGuard<T> {
-        RwLockReadGuard::map(self.0.read(), |(r, _)| r)
+    /// Collect the callbacks which [`needs_update`](Self::needs_update) would run.
+    pub(crate) fn collect_updates(&self, updates: &mut LinkUpdates) {
+        self.listeners.lock().retain(|id, (ct, once, u)| {
+            if *ct > 0 || *once {
+                updates.scopes.entry(*id).or_insert_with(|| u.clone());
+            }
+            *once = false;
+            *ct > 0
+        });
+        updates
+            .watchers
+            .extend(self.watchers.read().iter().map(|(id, w)| (*id, w.clone())));
+    }
+    pub(crate) fn borrow(&self) -> MappedRwLockReadGuard<'_, T> {
+        RwLockReadGuard::map(self.value.read(), |r| r)
     }
-    pub(crate) fn borrow_mut(&self) -> MappedRwLockWriteGuard<T> {
-        RwLockWriteGuard::map(self.0.write(), |(r, _)| r)
+    pub(crate) fn borrow_mut(&self) -> MappedRwLockWriteGuard<'_, T> {
+        RwLockWriteGuard::map(self.value.write(), |r| r)
     }
 }
 #[cfg(feature = "debug")]
 impl<T: std::fmt::Debug> std::fmt::Debug for Link<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Ok(me) = (self.0).try_borrow() {
-            write!(f, "Link({:?})", me.0)
+        if let Ok(me) = (self.value).try_borrow() {
+            write!(f, "Link({:?})", *me)
         } else {
             f.write_str("Link::AlreadyBorrowed")
         }
@@ -99,6 +271,11 @@ impl<T> Shareable<T> {
         Self(None)
     }
 }
+impl<T> Default for Shareable<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 #[cfg(feature = "debug")]
 impl<T: std::fmt::Debug> std::fmt::Debug for Shareable<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -150,6 +327,18 @@ macro_rules! shareable {
             pub fn use_w<'a, P>(self,cx: &$crate::reexported::Scope<'a, P>) -> &'a mut $crate::Shared<$Ty, $crate::W> {
                 $crate::shared::Static::_use_w(self, cx)
             }
+            /// Obtain a RW pointer to a shared [`List`]($crate::List) which also listens to its
+            /// entries.
+            ///
+            /// `cx` will be marked as needing update each time the list is written to, and also
+            /// each time any entry of the list is written to. Entries which are added to the list
+            /// later are listened to as well.
+            pub fn use_rw_deep<'a, P, __T: 'static + Send + Sync>(self, cx: &$crate::reexported::Scope<'a, P>) -> &'a mut $crate::Shared<$crate::List<__T>, $crate::RW>
+            where
+                Self: $crate::shared::Static<Type = $crate::List<__T>>,
+            {
+                $crate::List::_use_rw_deep(self, cx)
+            }
             /// Get a pointer to the value, but don't call 'use_hook'.
             ///
             /// This is generally to be avoided in components, but should be used when the shared
@@ -242,18 +431,33 @@ impl<T: 'static, B: 'static + super::Flag> Shared<T, B> {
     }
     /// Obtain a write pointer to the shared value and register the change.
     ///
-    /// This will mark all components which hold a RW link to the value as needing update.
-    pub fn write(&self) -> MappedRwLockWriteGuard<T> {
-        self.link.needs_update();
-        self.link.borrow_mut()
+    /// This will mark all components which hold a RW link to the value as needing update once the
+    /// returned guard is dropped.
+    pub fn write(&self) -> WriteGuard<'_, T> {
+        WriteGuard {
+            guard: Some(self.link.borrow_mut()),
+            link: &self.link,
+            _defer: DeferWatchers::new(),
+        }
     }
     /// Obtain a write pointer to the shared value but do not register the change.
     ///
     /// This will not notify consumers of the change to the value.
-    pub fn write_silent(&self) -> MappedRwLockWriteGuard<T> {
+    pub fn write_silent(&self) -> MappedRwLockWriteGuard<'_, T> {
         self.link.borrow_mut()
     }
     /// Mark the components which hold a RW link to the value as needing update.
+    ///
+    /// This doesn't lock the value, so it may be called while the value is borrowed:
+    ///
+    /// ```rust
+    /// dioxus_shareables::shareable!(Var: usize = 900);
+    ///
+    /// let var = Var.share();
+    /// let r = var.read();
+    /// var.needs_update();
+    /// assert_eq!(*r, 900);
+    /// ```
     pub fn needs_update(&self) {
         self.link.needs_update();
     }
@@ -284,24 +488,158 @@ impl<T: 'static, B: 'static + super::Flag> Shared<T, B> {
             *self.write() = updated;
         }
     }
+    /// Mark scope `cx` as needing update the next time the shared value is written to.
+    ///
+    /// Unlike [`use_rw`](crate::shareable), this is not a hook, so it may be called any number of
+    /// times, e.g. inside a loop over the entries of a [`List`](crate::List). The subscription
+    /// lasts only until the next update, so a component should call this each time it renders
+    /// and reads the value:
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// use dioxus_shareables::{shareable, List};
+    ///
+    /// shareable!(Numbers: List<usize> = [3, 5, 7].into_iter().collect());
+    ///
+    /// #[allow(non_snake_case)]
+    /// fn Evens(cx: Scope) -> Element {
+    ///     let nums = Numbers.use_rw(&cx);
+    ///     let evens = nums
+    ///         .read()
+    ///         .iter()
+    ///         .filter(|n| *n.subscribe(&cx).read() % 2 == 0) // Updated if any of these change.
+    ///         .count();
+    ///     cx.render(rsx! { "There are {evens} even numbers." })
+    /// }
+    /// # let mut dom = VirtualDom::new(Evens);
+    /// # let _ = dom.rebuild();
+    /// # let entry = Numbers.share().read().get(0).unwrap().share();
+    /// # assert_eq!(entry.listeners(), "[(0, 0)]");
+    /// # *entry.write() = 4;
+    /// # assert_eq!(entry.listeners(), "[]");
+    /// ```
+    pub fn subscribe_scope<P>(&self, cx: &dioxus_core::Scope<P>) -> &Self {
+        self.link
+            .add_listener_once(cx.scope_id().0, || cx.schedule_update());
+        self
+    }
+    /// Check whether the shared value has been removed from the [`List`](crate::List) it belonged
+    /// to.
+    ///
+    /// When an entry is removed from a list (e.g. by [`List::remove`](crate::List::remove),
+    /// [`List::retain`](crate::List::retain) or [`List::clear`](crate::List::clear), or because
+    /// the list itself was dropped), components holding a RW link to it are marked as needing
+    /// update, so that they can check this and e.g. close an editor for the entry.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// use dioxus_shareables::{shareable, List};
+    ///
+    /// shareable!(Numbers: List<usize> = [3, 5, 7].into_iter().collect());
+    ///
+    /// let entry = Numbers.share().read().get(1).unwrap().share();
+    /// assert!(!entry.is_detached());
+    /// Numbers.share().write().retain(|n| *n != 5);
+    /// assert!(entry.is_detached());
+    /// ```
+    ///
+    /// Values declared with [`shareable!`](crate::shareable) are never detached.
+    pub fn is_detached(&self) -> bool {
+        self.link.is_detached()
+    }
+    /// Whether `self` and `other` point to the same shared value.
+    pub fn ptr_eq<C: 'static>(&self, other: &Shared<T, C>) -> bool {
+        Arc::ptr_eq(&self.link, &other.link)
+    }
     /// Get the value of the shared data.
-    pub fn read(&self) -> MappedRwLockReadGuard<T> {
+    pub fn read(&self) -> MappedRwLockReadGuard<'_, T> {
         self.link.borrow()
     }
     pub fn listeners(&self) -> String {
         format!(
             "{:?}",
             self.link
-                .0
-                .read()
-                .1
+                .listeners
+                .lock()
                 .iter()
-                .map(|(&i, &(j, _))| (i, j))
+                .map(|(&i, &(j, _, _))| (i, j))
                 .collect::<Vec<_>>()
         )
     }
 }
 
+impl<T: 'static, B: 'static> Shared<T, B> {
+    #[doc(hidden)]
+    pub fn downgrade(&self) -> WeakShared<T> {
+        WeakShared(Arc::downgrade(&self.link))
+    }
+}
+
+/// A handle to a shared value which doesn't keep it alive.
+#[doc(hidden)]
+pub struct WeakShared<T>(Weak<Link<T>>);
+impl<T: 'static> WeakShared<T> {
+    pub fn upgrade(&self) -> Option<Shared<T, super::W>> {
+        self.0.upgrade().map(Shared::from_link)
+    }
+}
+
+/// Shared values which can be watched (see [`watch`].)
+#[doc(hidden)]
+pub trait Watch {
+    fn add_watcher_as(&self, id: usize, f: Arc<dyn Send + Sync + Fn()>);
+}
+impl<T: 'static, B: 'static> Watch for Shared<T, B> {
+    fn add_watcher_as(&self, id: usize, f: Arc<dyn Send + Sync + Fn()>) {
+        self.link.add_watcher_as(id, f)
+    }
+}
+/// Run `f` after each change to any of `sources` (once, when several of them are changed
+/// together.) This keeps the computed fields of a [`shareable_struct`](crate::shareable_struct)
+/// up to date.
+///
+/// `f` only runs once the thread holds no write guards, so it can read the sources even if they
+/// were written while another source was borrowed.
+#[doc(hidden)]
+pub fn watch<F: 'static + Send + Sync + Fn()>(sources: &[&dyn Watch], f: F) {
+    let id = next_watcher_id();
+    let f = Arc::new(f);
+    for s in sources {
+        s.add_watcher_as(id, f.clone());
+    }
+}
+
+/// A shared value which can only be read: a computed field of a
+/// [`shareable_struct`](crate::shareable_struct), or the contents of a
+/// [`ListView`](crate::ListView) or a [`GridLine`](crate::GridLine), for example.
+#[repr(transparent)]
+pub struct ReadOnly<T: 'static, B: 'static>(Shared<T, B>);
+impl<T: 'static, B: 'static> Clone for ReadOnly<T, B> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+impl<T: 'static> ReadOnly<T, super::W> {
+    pub(crate) fn from_link(link: Arc<Link<T>>) -> Self {
+        Self(Shared::from_link(link))
+    }
+}
+impl<T: 'static, B: 'static + super::Flag> ReadOnly<T, B> {
+    #[doc(hidden)]
+    pub fn new(shared: &Shared<T, B>) -> &Self {
+        // SAFETY: `ReadOnly` is a transparent wrapper.
+        unsafe { &*(shared as *const Shared<T, B> as *const Self) }
+    }
+    /// Get the value of the shared data.
+    pub fn read(&self) -> MappedRwLockReadGuard<'_, T> {
+        self.0.read()
+    }
+    /// Whether the value has been detached. See [`Shared::is_detached`].
+    pub fn is_detached(&self) -> bool {
+        self.0.is_detached()
+    }
+}
+
 impl<T: 'static> Shared<T, super::W> {
     pub(crate) fn from_link(link: Arc<Link<T>>) -> Self {
         Self {
@@ -330,6 +668,41 @@ impl<T: 'static> Shared<T, super::W> {
     }
 }
 
+/// A write pointer to a shared value.
+///
+/// This is returned by [`Shared::write`]; components which hold a RW link to the value are marked
+/// as needing update when the guard is dropped, which must happen on the thread which took it:
+/// ```compile_fail
+/// dioxus_shareables::shareable!(Var: usize = 900);
+/// let var = Var.share();
+/// let w = var.write();
+/// std::thread::scope(|s| { s.spawn(move || drop(w)); }); // error: `WriteGuard` cannot be sent between threads
+/// ```
+pub struct WriteGuard<'a, T> {
+    guard: Option<MappedRwLockWriteGuard<'a, T>>,
+    link: &'a Link<T>,
+    // Dropped after the link has been updated, which runs the watchers if this was the last guard.
+    _defer: DeferWatchers,
+}
+impl<'a, T> std::ops::Deref for WriteGuard<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.guard.as_ref().expect("WriteGuard used after drop")
+    }
+}
+impl<'a, T> std::ops::DerefMut for WriteGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.guard.as_mut().expect("WriteGuard used after drop")
+    }
+}
+impl<'a, T> Drop for WriteGuard<'a, T> {
+    fn drop(&mut self) {
+        // Release the lock before notifying so that watchers can read the new value.
+        self.guard.take();
+        self.link.needs_update();
+    }
+}
+
 impl<T: 'static, B: 'static> Drop for Shared<T, B> {
     fn drop(&mut self) {
         if let Some(id) = self.id {
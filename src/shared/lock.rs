@@ -0,0 +1,240 @@
+//! mod `lock` - locking several links at once.
+//!
+//! This is what lets a [`shareable_struct`](crate::shareable_struct) read or write all of its
+//! fields consistently (in `snapshot`, `restore` and `transaction`.)
+
+use super::{DeferWatchers, Link, LinkUpdates, Shared};
+use std::cell::Cell;
+use std::sync::Arc;
+
+/// A link of any type, as far as locking it is concerned.
+trait AnyLink: Send + Sync {
+    /// Lock the link, without holding a guard.
+    fn lock(&self, write: bool);
+    /// Unlock a link locked with [`lock`](Self::lock).
+    ///
+    /// # Safety
+    /// The link must be locked by the caller, with the same `write`.
+    unsafe fn unlock(&self, write: bool);
+    fn collect_updates(&self, updates: &mut LinkUpdates);
+}
+impl<T: Send + Sync> AnyLink for Link<T> {
+    fn lock(&self, write: bool) {
+        if write {
+            std::mem::forget(self.value.write())
+        } else {
+            std::mem::forget(self.value.read())
+        }
+    }
+    unsafe fn unlock(&self, write: bool) {
+        if write {
+            self.value.force_unlock_write()
+        } else {
+            self.value.force_unlock_read()
+        }
+    }
+    fn collect_updates(&self, updates: &mut LinkUpdates) {
+        Link::collect_updates(self, updates)
+    }
+}
+
+/// The address of the value behind a link, which identifies the link.
+fn addr<T>(link: &Arc<Link<T>>) -> usize {
+    Arc::as_ptr(link) as *const () as usize
+}
+
+/// A set of links to be locked together.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Links(Vec<(usize, Arc<dyn AnyLink>)>);
+impl Links {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn add<T: 'static + Send + Sync, B: 'static>(&mut self, shared: &Shared<T, B>) {
+        self.0.push((addr(&shared.link), shared.link.clone()));
+    }
+    /// Lock every link in the set.
+    ///
+    /// Links are locked in the order of their addresses, so that two sets of links being locked
+    /// at the same time can't deadlock, and a link which was added more than once is only locked
+    /// once.
+    pub fn lock(mut self, write: bool) -> Locked {
+        self.0.sort_by_key(|(a, _)| *a);
+        self.0.dedup_by_key(|(a, _)| *a);
+        let links = self
+            .0
+            .into_iter()
+            .map(|(addr, link)| {
+                link.lock(write);
+                LockedLink {
+                    addr,
+                    link,
+                    borrows: Cell::new(0),
+                    written: Cell::new(false),
+                }
+            })
+            .collect();
+        Locked {
+            links,
+            write,
+            _defer: DeferWatchers::new(),
+        }
+    }
+}
+
+struct LockedLink {
+    addr: usize,
+    link: Arc<dyn AnyLink>,
+    /// The number of `LockedRef`s to the value, or -1 while there's a `LockedMut`.
+    borrows: Cell<isize>,
+    written: Cell<bool>,
+}
+
+/// A set of locked links.
+///
+/// The values of the links can only be accessed through the set while it is held, and are
+/// borrowed from it like from a `RefCell`. If the links were locked for writing, then once they
+/// are all unlocked each component which holds a RW link to any of the values which were written
+/// is marked as needing update once.
+///
+/// The set must be unlocked on the thread which locked it:
+/// ```compile_fail
+/// let locked = dioxus_shareables::shared::Links::new().lock(true);
+/// std::thread::spawn(move || drop(locked)); // error: `Locked` cannot be sent between threads
+/// ```
+#[doc(hidden)]
+pub struct Locked {
+    links: Vec<LockedLink>,
+    write: bool,
+    _defer: DeferWatchers,
+}
+impl Locked {
+    fn find<T>(&self, link: &Arc<Link<T>>) -> &LockedLink {
+        let a = addr(link);
+        match self.links.binary_search_by_key(&a, |l| l.addr) {
+            Ok(i) => &self.links[i],
+            Err(_) => panic!("the link is not locked by this set"),
+        }
+    }
+    pub fn read<'a, T: 'static, B: 'static>(
+        &'a self,
+        shared: &'a Shared<T, B>,
+    ) -> LockedRef<'a, T> {
+        self.read_link(&shared.link)
+    }
+    pub fn write<'a, T: 'static, B: 'static>(
+        &'a self,
+        shared: &'a Shared<T, B>,
+    ) -> LockedMut<'a, T> {
+        self.write_link(&shared.link)
+    }
+    fn read_link<'a, T>(&'a self, link: &'a Arc<Link<T>>) -> LockedRef<'a, T> {
+        let locked = self.find(link);
+        let borrows = locked.borrows.get();
+        assert!(borrows >= 0, "the value is already being written");
+        locked.borrows.set(borrows + 1);
+        // SAFETY: the link is locked until `self` is dropped, and there's no `LockedMut` to it.
+        let value = unsafe { &*link.value.data_ptr() };
+        LockedRef {
+            value,
+            borrows: &locked.borrows,
+        }
+    }
+    fn write_link<'a, T>(&'a self, link: &'a Arc<Link<T>>) -> LockedMut<'a, T> {
+        assert!(self.write, "the links are locked for reading");
+        let locked = self.find(link);
+        assert!(locked.borrows.get() == 0, "the value is already borrowed");
+        locked.borrows.set(-1);
+        locked.written.set(true);
+        // SAFETY: the link is locked for writing until `self` is dropped, and there's no other
+        // `LockedRef` or `LockedMut` to it.
+        let value = unsafe { &mut *link.value.data_ptr() };
+        LockedMut {
+            value,
+            borrows: &locked.borrows,
+        }
+    }
+}
+impl Drop for Locked {
+    fn drop(&mut self) {
+        for l in &self.links {
+            // SAFETY: every link was locked by `Links::lock`.
+            unsafe { l.link.unlock(self.write) }
+        }
+        let mut updates = LinkUpdates::default();
+        for l in self.links.iter().filter(|l| l.written.get()) {
+            l.link.collect_updates(&mut updates);
+        }
+        updates.run();
+    }
+}
+
+/// A field of a struct in a transaction (see [`shareable_struct`](crate::shareable_struct).)
+pub struct LockedField<'a, T> {
+    locked: &'a Locked,
+    link: &'a Arc<Link<T>>,
+}
+impl<'a, T: 'static> LockedField<'a, T> {
+    #[doc(hidden)]
+    pub fn new<B: 'static>(locked: &'a Locked, shared: &'a Shared<T, B>) -> Self {
+        Self {
+            locked,
+            link: &shared.link,
+        }
+    }
+    /// Read the value.
+    ///
+    /// # Panics
+    /// If the value is being written in the same transaction.
+    pub fn read(&self) -> LockedRef<'a, T> {
+        self.locked.read_link(self.link)
+    }
+    /// Write the value. Components which hold a RW link to it are marked as needing update at the
+    /// end of the transaction.
+    ///
+    /// # Panics
+    /// If the value is being read or written elsewhere in the same transaction.
+    pub fn write(&self) -> LockedMut<'a, T> {
+        self.locked.write_link(self.link)
+    }
+}
+
+/// A read guard for a field in a transaction.
+pub struct LockedRef<'a, T> {
+    value: &'a T,
+    borrows: &'a Cell<isize>,
+}
+impl<'a, T> std::ops::Deref for LockedRef<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+impl<'a, T> Drop for LockedRef<'a, T> {
+    fn drop(&mut self) {
+        self.borrows.set(self.borrows.get() - 1);
+    }
+}
+
+/// A write guard for a field in a transaction.
+pub struct LockedMut<'a, T> {
+    value: &'a mut T,
+    borrows: &'a Cell<isize>,
+}
+impl<'a, T> std::ops::Deref for LockedMut<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+impl<'a, T> std::ops::DerefMut for LockedMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
+impl<'a, T> Drop for LockedMut<'a, T> {
+    fn drop(&mut self) {
+        self.borrows.set(0);
+    }
+}
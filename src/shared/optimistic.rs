@@ -0,0 +1,144 @@
+//! mod `optimistic` - optimistic updates of shared values.
+//!
+//! See [`Shared::optimistic`] for more info.
+
+use super::{Link, Shared};
+use std::sync::Arc;
+
+/// The optimistic updates pending on a [`Link`], along with the value they were applied to.
+pub(crate) struct Layers<T> {
+    base: T,
+    next: usize,
+    pending: Vec<Layer<T>>,
+}
+struct Layer<T> {
+    id: usize,
+    committed: bool,
+    apply: Box<dyn Send + FnMut(&mut T)>,
+}
+
+impl<T: 'static + Clone, B: 'static + crate::Flag> Shared<T, B> {
+    /// Apply `f` to the shared value optimistically, i.e. before knowing whether the change will
+    /// stick.
+    ///
+    /// The change is visible immediately. The returned token should later be
+    /// [`commit`](Optimistic::commit)ted (e.g. when a server accepts the change) or
+    /// [`rollback`](Optimistic::rollback)ed (when it doesn't), which undoes the change and marks
+    /// components which hold a RW link to the value as needing update.
+    ///
+    /// Several optimistic updates may be pending at once, and they may be resolved in any order:
+    /// the visible value is always the value before the first pending update with all the updates
+    /// which have not been rolled back applied in the order they were made. This is why `f` may
+    /// be called more than once.
+    ///
+    /// ```rust
+    /// use dioxus_shareables::shareable;
+    ///
+    /// shareable!(Likes: Vec<&'static str> = vec![]);
+    ///
+    /// let likes = Likes.share();
+    /// let a = likes.optimistic(|l| l.push("a"));
+    /// let b = likes.optimistic(|l| l.push("b"));
+    /// let c = likes.optimistic(|l| l.push("c"));
+    /// assert_eq!(*likes.read(), ["a", "b", "c"]);
+    ///
+    /// b.rollback();
+    /// assert_eq!(*likes.read(), ["a", "c"]);
+    /// c.commit();
+    /// a.rollback();
+    /// assert_eq!(*likes.read(), ["c"]);
+    /// ```
+    ///
+    /// Note that changes made with [`write`](Self::write) while optimistic updates are pending
+    /// are lost when one of them is rolled back.
+    pub fn optimistic<F: 'static + Send + FnMut(&mut T)>(&self, mut f: F) -> Optimistic<T> {
+        let id = {
+            let mut layers = self.link.optimistic.lock();
+            let mut value = self.link.borrow_mut();
+            let layers = layers.get_or_insert_with(|| Layers {
+                base: value.clone(),
+                next: 0,
+                pending: Vec::new(),
+            });
+            f(&mut value);
+            let id = layers.next;
+            layers.next += 1;
+            layers.pending.push(Layer {
+                id,
+                committed: false,
+                apply: Box::new(f),
+            });
+            id
+        };
+        self.link.needs_update();
+        Optimistic {
+            link: self.link.clone(),
+            id,
+            resolved: false,
+        }
+    }
+}
+
+/// A pending optimistic update, created by [`Shared::optimistic`].
+///
+/// Dropping the token without resolving it rolls the update back.
+#[must_use = "dropping the token rolls the update back"]
+pub struct Optimistic<T: 'static + Clone> {
+    link: Arc<Link<T>>,
+    id: usize,
+    resolved: bool,
+}
+impl<T: 'static + Clone> Optimistic<T> {
+    /// Keep the update.
+    pub fn commit(mut self) {
+        self.resolve(true)
+    }
+    /// Undo the update.
+    pub fn rollback(mut self) {
+        self.resolve(false)
+    }
+    fn resolve(&mut self, commit: bool) {
+        if std::mem::replace(&mut self.resolved, true) {
+            return;
+        }
+        let changed = {
+            let mut guard = self.link.optimistic.lock();
+            let Some(layers) = guard.as_mut() else {
+                return;
+            };
+            let Some(i) = layers.pending.iter().position(|l| l.id == self.id) else {
+                return;
+            };
+            if commit {
+                layers.pending[i].committed = true;
+            } else {
+                layers.pending.remove(i);
+            }
+            // Committed updates which are not preceded by a pending one are final, so they can be
+            // folded into the base value.
+            while layers.pending.first().is_some_and(|l| l.committed) {
+                let mut l = layers.pending.remove(0);
+                (l.apply)(&mut layers.base);
+            }
+            if !commit {
+                let mut value = self.link.borrow_mut();
+                *value = layers.base.clone();
+                for l in &mut layers.pending {
+                    (l.apply)(&mut value)
+                }
+            }
+            if layers.pending.is_empty() {
+                *guard = None;
+            }
+            !commit
+        };
+        if changed {
+            self.link.needs_update()
+        }
+    }
+}
+impl<T: 'static + Clone> Drop for Optimistic<T> {
+    fn drop(&mut self) {
+        self.resolve(false)
+    }
+}
@@ -0,0 +1,61 @@
+//! mod `registry` - storage for shared values whose type depends on generic parameters.
+//!
+//! A `static` can't mention the generic parameters of the item it's declared in, so the fields of
+//! a generic [`shareable_struct`](crate::shareable_struct) are stored in a registry instead, keyed
+//! by a type which is distinct for each field and each set of generic arguments.
+
+use super::{Link, Shareable};
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+use std::any::{Any, TypeId};
+use std::sync::Arc;
+
+type Links = FxHashMap<TypeId, Box<dyn Any + Send + Sync>>;
+type Slot<T> = Arc<Mutex<Shareable<T>>>;
+
+#[doc(hidden)]
+pub struct Registry(Mutex<Option<Links>>);
+impl Registry {
+    pub const fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+    /// Get the storage for the value keyed by `K`, initializing it with `f` if needed.
+    ///
+    /// Like a [`shareable!`](crate::shareable) value, the value is initialized at most once, even
+    /// if several threads ask for it at the same time.
+    /// ```
+    /// # use std::sync::atomic::{AtomicUsize, Ordering};
+    /// # static REGISTRY: dioxus_shareables::shared::Registry = dioxus_shareables::shared::Registry::new();
+    /// # static INITS: AtomicUsize = AtomicUsize::new(0);
+    /// # std::thread::scope(|s| for _ in 0..4 {
+    /// #     s.spawn(|| REGISTRY.shareable::<(), usize, _>(|| {
+    /// #         std::thread::sleep(std::time::Duration::from_millis(20));
+    /// #         INITS.fetch_add(1, Ordering::Relaxed)
+    /// #     }));
+    /// # });
+    /// # assert_eq!(INITS.load(Ordering::Relaxed), 1);
+    /// ```
+    pub fn shareable<K: 'static, T: 'static + Send + Sync, F: FnOnce() -> T>(
+        &self,
+        f: F,
+    ) -> Shareable<T> {
+        let slot = self
+            .0
+            .lock()
+            .get_or_insert_with(Default::default)
+            .entry(TypeId::of::<K>())
+            .or_insert_with(|| Box::new(Slot::<T>::default()))
+            .downcast_ref::<Slot<T>>()
+            .expect("a registry key is only ever used with one type")
+            .clone();
+        // Only the slot is locked while initializing, since `f` may itself use the registry.
+        let mut slot = slot.lock();
+        let link = slot.0.get_or_insert_with(|| Arc::new(Link::new(f())));
+        Shareable(Some(link.clone()))
+    }
+}
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
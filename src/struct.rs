@@ -2,12 +2,20 @@ pub trait ShareableStruct: Sized {
     type Fields;
     type Actions;
 }
-pub trait FieldOf<S: ShareableStruct> {
+pub trait FieldOf {
     type RWType;
     type WType;
+    type RType;
     const RW: Self::RWType;
     const W: Self::WType;
-}
+    const R: Self::RType;
+}
+/// What `W[..]` gives a computed field in `struct_actions!` (which is not an action.)
+#[derive(Clone, Copy)]
+pub struct ComputedFieldsCantBeWritten;
+/// What `R[..]` gives a field which isn't computed in `struct_actions!` (which is not an action.)
+#[derive(Clone, Copy)]
+pub struct OnlyComputedFieldsAreReadOnly;
 pub trait InitWith<O: super::InitType>: super::InitType {
     type Flag: super::InitType;
 }
@@ -41,43 +49,273 @@ impl InitWith<super::RW> for super::RW {
 pub trait ImpliesInitField<O: super::InitType>: super::InitType {}
 impl<A: super::InitType, B: InitWith<A, Flag = A>> ImpliesInitField<B> for A {}
 
-pub trait InitField<F> {
-    type Flag: super::InitType;
-}
-impl<F> InitField<F> for () {
-    type Flag = ();
+/// The flags an action gives each field of a struct, along with the actions it gives each
+/// substruct.
+///
+/// Each struct has its own record type; an action is turned into a record by merging the records
+/// of its items.
+pub trait EmptyRecord: Copy {
+    /// The record of an action which gives nothing to any field.
+    const EMPTY: Self;
+}
+pub trait Merge<O>: Copy {
+    type Output: Copy;
+    fn merge(self, o: O) -> Self::Output;
+}
+/// Actions which can be turned into records with the given empty record.
+pub trait CollectInto<R: EmptyRecord> {
+    type Record: Copy;
+    fn record(&self) -> Self::Record;
+}
+impl<R: EmptyRecord> CollectInto<R> for () {
+    type Record = R;
+    fn record(&self) -> R {
+        R::EMPTY
+    }
 }
-impl<F, T: super::InductiveMarkerTuple> InitField<F> for T
+impl<R: EmptyRecord, T: super::InductiveMarkerTuple> CollectInto<R> for T
 where
-    T::Step: InitField<F>,
-    T::Base: InitField<F>,
-    <T::Base as InitField<F>>::Flag: InitWith<<T::Step as InitField<F>>::Flag>,
+    T::Base: CollectInto<R>,
+    T::Step: CollectInto<R>,
+    <T::Base as CollectInto<R>>::Record: Merge<<T::Step as CollectInto<R>>::Record>,
 {
-    type Flag =
-        <<T::Base as InitField<F>>::Flag as InitWith<<T::Step as InitField<F>>::Flag>>::Flag;
+    type Record =
+        <<T::Base as CollectInto<R>>::Record as Merge<<T::Step as CollectInto<R>>::Record>>::Output;
+    fn record(&self) -> Self::Record {
+        self.base().record().merge(self.step().record())
+    }
 }
-pub trait InitSubstruct<F> {
-    type Actions;
-    fn substruct_actions(&self) -> Self::Actions;
+
+/// The flags of (up to) sixteen fields in a record.
+///
+/// Records group their fields in chunks, so that merging the record of one item into the record
+/// of an action only touches the chunk of the item: a chunk whose flags are all `()` is just `()`.
+#[allow(clippy::type_complexity)]
+pub struct Chunk<F0, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15>(
+    std::marker::PhantomData<
+        fn() -> (
+            F0,
+            F1,
+            F2,
+            F3,
+            F4,
+            F5,
+            F6,
+            F7,
+            F8,
+            F9,
+            F10,
+            F11,
+            F12,
+            F13,
+            F14,
+            F15,
+        ),
+    >,
+);
+impl<F0, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15>
+    Chunk<F0, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15>
+{
+    pub const fn new() -> Self {
+        Self(std::marker::PhantomData)
+    }
 }
-impl<F> InitSubstruct<F> for () {
-    type Actions = ();
-    fn substruct_actions(&self) {}
+impl<F0, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15> Default
+    for Chunk<F0, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<F0, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15> Clone
+    for Chunk<F0, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15>
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<F0, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15> Copy
+    for Chunk<F0, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15>
+{
+}
+pub trait ChunkFlags: Copy {
+    type F0: super::InitType;
+    type F1: super::InitType;
+    type F2: super::InitType;
+    type F3: super::InitType;
+    type F4: super::InitType;
+    type F5: super::InitType;
+    type F6: super::InitType;
+    type F7: super::InitType;
+    type F8: super::InitType;
+    type F9: super::InitType;
+    type F10: super::InitType;
+    type F11: super::InitType;
+    type F12: super::InitType;
+    type F13: super::InitType;
+    type F14: super::InitType;
+    type F15: super::InitType;
+}
+impl ChunkFlags for () {
+    type F0 = ();
+    type F1 = ();
+    type F2 = ();
+    type F3 = ();
+    type F4 = ();
+    type F5 = ();
+    type F6 = ();
+    type F7 = ();
+    type F8 = ();
+    type F9 = ();
+    type F10 = ();
+    type F11 = ();
+    type F12 = ();
+    type F13 = ();
+    type F14 = ();
+    type F15 = ();
+}
+impl<
+        F0: super::InitType,
+        F1: super::InitType,
+        F2: super::InitType,
+        F3: super::InitType,
+        F4: super::InitType,
+        F5: super::InitType,
+        F6: super::InitType,
+        F7: super::InitType,
+        F8: super::InitType,
+        F9: super::InitType,
+        F10: super::InitType,
+        F11: super::InitType,
+        F12: super::InitType,
+        F13: super::InitType,
+        F14: super::InitType,
+        F15: super::InitType,
+    > ChunkFlags for Chunk<F0, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15>
+{
+    type F0 = F0;
+    type F1 = F1;
+    type F2 = F2;
+    type F3 = F3;
+    type F4 = F4;
+    type F5 = F5;
+    type F6 = F6;
+    type F7 = F7;
+    type F8 = F8;
+    type F9 = F9;
+    type F10 = F10;
+    type F11 = F11;
+    type F12 = F12;
+    type F13 = F13;
+    type F14 = F14;
+    type F15 = F15;
+}
+impl<X: Copy> Merge<X> for () {
+    type Output = X;
+    fn merge(self, o: X) -> X {
+        o
+    }
 }
-impl<F, T: super::InductiveMarkerTuple> InitSubstruct<F> for T
+impl<
+        F0: super::InitType,
+        F1: super::InitType,
+        F2: super::InitType,
+        F3: super::InitType,
+        F4: super::InitType,
+        F5: super::InitType,
+        F6: super::InitType,
+        F7: super::InitType,
+        F8: super::InitType,
+        F9: super::InitType,
+        F10: super::InitType,
+        F11: super::InitType,
+        F12: super::InitType,
+        F13: super::InitType,
+        F14: super::InitType,
+        F15: super::InitType,
+    > Merge<()> for Chunk<F0, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15>
+{
+    type Output = Self;
+    fn merge(self, _: ()) -> Self {
+        self
+    }
+}
+impl<
+        F0: super::InitType,
+        F1: super::InitType,
+        F2: super::InitType,
+        F3: super::InitType,
+        F4: super::InitType,
+        F5: super::InitType,
+        F6: super::InitType,
+        F7: super::InitType,
+        F8: super::InitType,
+        F9: super::InitType,
+        F10: super::InitType,
+        F11: super::InitType,
+        F12: super::InitType,
+        F13: super::InitType,
+        F14: super::InitType,
+        F15: super::InitType,
+        G0: super::InitType,
+        G1: super::InitType,
+        G2: super::InitType,
+        G3: super::InitType,
+        G4: super::InitType,
+        G5: super::InitType,
+        G6: super::InitType,
+        G7: super::InitType,
+        G8: super::InitType,
+        G9: super::InitType,
+        G10: super::InitType,
+        G11: super::InitType,
+        G12: super::InitType,
+        G13: super::InitType,
+        G14: super::InitType,
+        G15: super::InitType,
+    > Merge<Chunk<G0, G1, G2, G3, G4, G5, G6, G7, G8, G9, G10, G11, G12, G13, G14, G15>>
+    for Chunk<F0, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15>
 where
-    T::Step: InitSubstruct<F> + Copy,
-    T::Base: InitSubstruct<F> + Copy,
+    F0: InitWith<G0>,
+    F1: InitWith<G1>,
+    F2: InitWith<G2>,
+    F3: InitWith<G3>,
+    F4: InitWith<G4>,
+    F5: InitWith<G5>,
+    F6: InitWith<G6>,
+    F7: InitWith<G7>,
+    F8: InitWith<G8>,
+    F9: InitWith<G9>,
+    F10: InitWith<G10>,
+    F11: InitWith<G11>,
+    F12: InitWith<G12>,
+    F13: InitWith<G13>,
+    F14: InitWith<G14>,
+    F15: InitWith<G15>,
 {
-    type Actions = (
-        <T::Base as InitSubstruct<F>>::Actions,
-        <T::Step as InitSubstruct<F>>::Actions,
-    );
-    fn substruct_actions(&self) -> Self::Actions {
-        (
-            self.base().substruct_actions(),
-            self.step().substruct_actions(),
-        )
+    type Output = Chunk<
+        <F0 as InitWith<G0>>::Flag,
+        <F1 as InitWith<G1>>::Flag,
+        <F2 as InitWith<G2>>::Flag,
+        <F3 as InitWith<G3>>::Flag,
+        <F4 as InitWith<G4>>::Flag,
+        <F5 as InitWith<G5>>::Flag,
+        <F6 as InitWith<G6>>::Flag,
+        <F7 as InitWith<G7>>::Flag,
+        <F8 as InitWith<G8>>::Flag,
+        <F9 as InitWith<G9>>::Flag,
+        <F10 as InitWith<G10>>::Flag,
+        <F11 as InitWith<G11>>::Flag,
+        <F12 as InitWith<G12>>::Flag,
+        <F13 as InitWith<G13>>::Flag,
+        <F14 as InitWith<G14>>::Flag,
+        <F15 as InitWith<G15>>::Flag,
+    >;
+    fn merge(
+        self,
+        _: Chunk<G0, G1, G2, G3, G4, G5, G6, G7, G8, G9, G10, G11, G12, G13, G14, G15>,
+    ) -> Self::Output {
+        Chunk::new()
     }
 }
 
@@ -90,46 +328,27 @@ where
 {
 }
 
-pub trait InitFieldAs<F, Flag: super::InitType>: InitField<F> {}
-impl<F, T: InitField<F>, Flag: super::InitType> InitFieldAs<F, Flag> for T where
-    <T as InitField<F>>::Flag: ImpliesInitField<Flag>
-{
+/// Structs whose fields (and substructs) can be copied into a snapshot.
+pub trait Snapshot {
+    type Snapshot;
+    /// Copy the fields, which must be locked by `locked`.
+    fn snapshot_locked(&self, locked: &crate::shared::Locked) -> Self::Snapshot;
 }
 
-/// &'static str is not allowed for const generics, but we can imitate a &'static [u8; 256] bound
-/// using a lot of ints here.
-pub trait AssocType<
-    const _0: u128,
-    const _1: u128,
-    const _2: u128,
-    const _3: u128,
-    const _4: u128,
-    const _5: u128,
-    const _6: u128,
-    const _7: u128,
-    const _8: u128,
-    const _9: u128,
-    const _10: u128,
-    const _11: u128,
-    const _12: u128,
-    const _13: u128,
-    const _14: u128,
-    const _15: u128,
->
-{
+/// A name, as a list of 16-byte segments, under which [`AssocType`] provides a type.
+///
+/// (`&'static str` is not allowed for const generics, so names are spelled out as integers.)
+pub struct Name<const S: u128, Next>(std::marker::PhantomData<fn() -> Next>);
+
+/// The type of a field or action of a struct, looked up by name.
+#[diagnostic::on_unimplemented(
+    message = "unknown field or action: `{Self}` has nothing under this name",
+    label = "no such field or action",
+    note = "check the spelling of the field or action, and which struct it is looked up in"
+)]
+pub trait AssocType<Name> {
     type Type;
 }
-pub const fn seg_str(s: &'static str, r: usize) -> u128 {
-    let mut i = 0usize;
-    let mut c = 0;
-    loop {
-        if i >= 16 || r + i >= s.len() {
-            return c;
-        }
-        c += (s.as_bytes()[r + i] as u128) << (8 * i);
-        i += 1;
-    }
-}
 
 /// Create a `struct` definition for a global.
 ///
@@ -154,6 +373,9 @@ pub const fn seg_str(s: &'static str, r: usize) -> u128 {
 /// ```
 /// NOTE: fields in the struct must be `Send + Sync`
 ///
+/// The same struct can also be declared as an ordinary Rust struct with the
+/// [`attr::shareable_struct`](crate::attr::shareable_struct) attribute.
+///
 /// First we declare the struct itself, then "actions" which represent different views of the
 /// struct. When we use the struct, we then have to declare which actions we need:
 ///
@@ -311,9 +533,9 @@ pub const fn seg_str(s: &'static str, r: usize) -> u128 {
 ///                                        // ident which refers to a declared action for the
 ///                                        // given struct.
 ///         action UBC = W[u] |s[B]; // N.B.: The syntax doesn't change if B isn't in scope... B is
-///                                  // accessed as an associated type of GlobalState.
-///                                  // If you get errors involving `AssocType` bounds, this is a
-///                                  // these |s[B] style bounds are the most likely candidtates.
+///                                  // accessed as an associated type of GlobalStateActions.
+///                                  // If you get "unknown field or action" errors, then
+///                                  // these |s[B] style bounds are the most likely candidates.
 ///     }
 ///     // ...
 ///     # #[allow(non_snake_case)]
@@ -325,664 +547,310 @@ pub const fn seg_str(s: &'static str, r: usize) -> u128 {
 ///         # cx.render(rsx! { div {} })
 ///     }
 /// ```
+///
+/// The fields of a shareable struct are global, but the same definition can also back any number
+/// of separate instances, e.g. for per-document or per-tab state. `new_instance` creates an
+/// instance with its own fields (a `GlobalStateInstance` for `GlobalState`), which components can
+/// then use with actions just like the global struct:
+/// ```
+///     # use dioxus::prelude::*;
+///     dioxus_shareables::shareable_struct! {
+///         pub struct Document {
+///             title: String = "untitled".into(),
+///             dirty: bool = false,
+///         }
+///         action HEADER = RW[title, dirty];
+///         action EDIT = W[dirty];
+///     }
+///
+///     #[allow(non_snake_case)]
+///     #[inline_props]
+///     fn Tab(cx: Scope, doc: DocumentInstance) -> Element {
+///         let doc = Document::use_in(&cx, doc, HEADER);
+///         let (title, dirty) = (doc.title().read().clone(), *doc.dirty().read());
+///         cx.render(rsx! { "{title}" if dirty { "*" } })
+///     }
+///
+///     let (first, second) = (Document::new_instance(), Document::new_instance());
+///     *first.title().write() = "notes.txt".into();
+///     *Document::share_in(&second, EDIT).dirty().write() = true;
+///     assert_eq!((first.title().read().as_str(), *first.dirty().read()), ("notes.txt", false));
+///     assert_eq!((second.title().read().as_str(), *second.dirty().read()), ("untitled", true));
+///     // The global fields are untouched.
+///     assert!(!*Document::share(EDIT).dirty().read());
+/// ```
+///
+/// Instances are also stored in a [`List`](crate::List): a component which is passed an entry of
+/// the list can use it with an action in the same way, so it is only updated when the fields it
+/// reads change:
+/// ```
+///     # use dioxus::prelude::*;
+///     use dioxus_shareables::{shareable, List, ListEntry};
+///
+///     dioxus_shareables::shareable_struct! {
+///         pub struct Todo {
+///             name: String = String::new(),
+///             done: bool = false,
+///         }
+///         action ROW = W[name] RW[done];
+///         action RENAME = W[name];
+///     }
+///     shareable!(Todos: List<TodoInstance> = List::new());
+///
+///     #[allow(non_snake_case)]
+///     #[inline_props]
+///     fn TodoRow(cx: Scope, todo: ListEntry<TodoInstance>) -> Element {
+///         let todo = Todo::use_entry(&cx, todo, ROW); // Only updated when `done` changes.
+///         let done = *todo.done().read();
+///         cx.render(rsx! {
+///             li {
+///                 input { r#type: "checkbox", checked: "{done}", onclick: |_| *todo.done().write() ^= true }
+///             }
+///         })
+///     }
+///
+///     let todo = TodoInstance::new();
+///     *todo.name().write() = "write docs".into();
+///     Todos.share().write().push(todo);
+///
+///     let entry = Todos.share().read().get(0).unwrap();
+///     Todo::share_entry(&entry, RENAME).name().write().push_str(" (soon)");
+///     assert_eq!(*entry.share().read().name().read(), "write docs (soon)");
+/// ```
+///
+/// Shareable structs may be generic. Each set of generic arguments gets its own storage (so
+/// `Cache<u32, String>` and `Cache<u32, usize>` below don't share any fields), while the actions
+/// are the same for all of them. Type parameters must be `'static`, and the fields must be
+/// `Send + Sync` as usual. (When naming the struct with a turbofish, leave the actions parameter
+/// to be inferred with `_`, or it will be the default.)
+/// ```
+///     # use dioxus::prelude::*;
+///     use std::{collections::HashMap, hash::Hash};
+///
+///     dioxus_shareables::shareable_struct! {
+///         pub struct Cache<K: Hash + Eq + Send + Sync, V: Send + Sync> {
+///             entries: HashMap<K, V> = HashMap::new(),
+///             hits: usize = 0,
+///         }
+///         action LOOKUP impl pub Lookup = W[hits] RW[entries];
+///         action INSERT = W[entries];
+///         action STATS = W[hits];
+///     }
+///     impl<K: Hash + Eq + Send + Sync, V: Clone + Send + Sync, Actions: CacheActions> Cache<K, V, Actions> {
+///         pub fn get(&self, k: &K) -> Option<V> where Actions: Lookup {
+///             let self_ = self.with_actions(LOOKUP);
+///             *self_.hits().write() += 1;
+///             self_.entries().read().get(k).cloned()
+///         }
+///     }
+///
+///     #[allow(non_snake_case)]
+///     fn Component(cx: Scope) -> Element {
+///         let name = Cache::<u32, String, _>::use_(&cx, LOOKUP).get(&1).unwrap_or_default();
+///         cx.render(rsx! { "{name}" })
+///     }
+///
+///     Cache::<u32, String, _>::share(INSERT).entries().write().insert(1, "one".into());
+///     assert!(Cache::<u32, usize, _>::share(INSERT).entries().read().is_empty());
+///     let mut dom = VirtualDom::new(Component);
+///     let _ = dom.rebuild();
+///     assert_eq!(*Cache::<u32, String, _>::share(STATS).hits().write(), 1);
+/// ```
+///
+/// Every struct also has two actions of its own: `ALL_RW`, which reads and writes every field (and
+/// every substruct, with its `ALL_RW` action), and `ALL_W`, which writes every field (and every
+/// substruct, with its `ALL_W` action). They are associated constants, e.g. `Editor::ALL_RW`, but
+/// are named as just `ALL_RW` in actions. An action can include other actions of the same struct
+/// by name, with or without `+`; where an action includes both `W` and `RW` for the same field,
+/// `RW` wins:
+/// ```
+///     # use dioxus::prelude::*;
+///     dioxus_shareables::shareable_struct! {
+///         pub struct Settings {
+///             theme: &'static str = "light",
+///             font_size: u8 = 12,
+///         }
+///     }
+///     dioxus_shareables::shareable_struct! {
+///         pub struct Editor {
+///             text: String = String::new(),
+///             cursor: usize = 0,
+///             |settings: Settings,
+///         }
+///         action MOVE = RW[cursor];
+///         action TYPE = MOVE + RW[text] W[cursor]; // Still reads `cursor`.
+///         action EDIT = W[text] W[cursor];
+///         action SETUP = EDIT + |settings[ALL_W];
+///     }
+///
+///     #[allow(non_snake_case)]
+///     fn Component(cx: Scope) -> Element {
+///         let editor = Editor::use_(&cx, Editor::ALL_RW);
+///         let (text, size) = (editor.text().read().clone(), *editor.settings().font_size().read());
+///         cx.render(rsx! { "{text} at {size}pt" })
+///     }
+///
+///     let editor = Editor::share(SETUP); // The same as `Editor::share(Editor::ALL_W)`.
+///     *editor.settings().font_size().write() = 14;
+///     editor.text().write().push_str("hello");
+///     *editor.cursor().write() = editor.text().read().len();
+///     assert_eq!(*Settings::share(Settings::ALL_W).font_size().read(), 14);
+///     assert_eq!(*Editor::share(EDIT).cursor().read(), 5);
+///     let mut dom = VirtualDom::new(Component);
+///     let _ = dom.rebuild();
+/// ```
+///
+/// Each struct also comes with a plain struct holding the values of its fields (a
+/// `GlobalStateSnapshot` for `GlobalState`), with the snapshots of its substructs nested inside.
+/// `snapshot()` copies every field into one (so the fields must be `Clone`), and `restore` writes
+/// one back. Either way all of the fields are locked at once, so a snapshot never mixes old and
+/// new values. Instances have `snapshot` and `restore` methods as well. Attributes written as
+/// `#[snapshot(..)]` on the struct are put on the snapshot:
+/// ```
+///     dioxus_shareables::shareable_struct! {
+///         #[snapshot(derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize))]
+///         pub struct Position {
+///             x: i32 = 0,
+///             y: i32 = 0,
+///         }
+///     }
+///     dioxus_shareables::shareable_struct! {
+///         #[snapshot(derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize))]
+///         pub struct Player {
+///             name: String = "anonymous".into(),
+///             |pos: Position,
+///         }
+///         action MOVE = |pos[ALL_W];
+///     }
+///
+///     let saved = Player::snapshot();
+///     *Player::share(MOVE).pos().x().write() = 3;
+///     assert_eq!(Player::snapshot().pos, PositionSnapshot { x: 3, y: 0 });
+///
+///     Player::restore(saved.clone());
+///     assert_eq!(*Position::share(Position::ALL_W).x().read(), 0);
+///     assert_eq!(serde_json::to_string(&saved).unwrap(), r#"{"name":"anonymous","pos":{"x":0,"y":0}}"#);
+///
+///     let player = Player::new_instance();
+///     player.restore(PlayerSnapshot { name: "ferris".into(), pos: PositionSnapshot { x: 1, y: 2 } });
+///     assert_eq!(player.snapshot().name, "ferris");
+///     assert_eq!(Player::snapshot(), saved); // The global fields are untouched.
+/// ```
+///
+/// Fields are usually written one at a time, so other threads can see some fields updated but not
+/// others, and each write notifies its components separately. `transaction` locks every field the
+/// actions give access to instead, and passes a `GlobalStateTransaction` with the same accessors
+/// to a closure; components are notified once the closure returns, once each, however many of the
+/// fields they use were written. (Don't use the struct itself within the transaction: its fields
+/// are locked until the transaction ends.)
+/// ```
+///     dioxus_shareables::shareable_struct! {
+///         pub struct Totals {
+///             count: usize = 0,
+///         }
+///     }
+///     dioxus_shareables::shareable_struct! {
+///         pub struct Account {
+///             balance: i64 = 100,
+///             history: Vec<i64> = Vec::new(),
+///             |totals: Totals,
+///         }
+///         action DEPOSIT impl pub Deposit = W[balance, history] |totals[ALL_W];
+///     }
+///     impl<A: AccountActions> Account<A> {
+///         pub fn deposit(&self, amount: i64) -> i64 where A: Deposit {
+///             self.with_actions(DEPOSIT).transaction(|tx| {
+///                 *tx.balance().write() += amount;
+///                 tx.history().write().push(amount);
+///                 *tx.totals().count().write() += 1;
+///                 *tx.balance().read()
+///             })
+///         }
+///     }
+///
+///     assert_eq!(Account::share(DEPOSIT).deposit(5), 105);
+///     let snapshot = Account::snapshot();
+///     assert_eq!((snapshot.balance, snapshot.history, snapshot.totals.count), (105, vec![5], 1));
+/// ```
+///
+/// A field declared as `computed(|s| ..) from [..]` is computed from other fields of the struct
+/// (which the function gets references to, as `s.a` and so on.) The value is cached, and computed
+/// again each time any of its sources is written (once per transaction.) Computed fields can only
+/// be read: their accessors return a [`ReadOnly`](crate::ReadOnly), and actions give them as
+/// `R[..]` (or `RW[..]`), which updates the component whenever the value is recomputed. They
+/// aren't part of snapshots or transactions. Instances keep their own computed fields.
+/// ```
+///     # use dioxus::prelude::*;
+///     dioxus_shareables::shareable_struct! {
+///         pub struct Cart {
+///             prices: Vec<u32> = vec![3, 4],
+///             discount: u32 = 0,
+///             total: u32 = computed(|s| s.prices.iter().sum::<u32>() - s.discount) from [prices, discount],
+///         }
+///         action TOTAL = R[total];
+///         action EDIT = W[prices, discount];
+///     }
+///
+///     #[allow(non_snake_case)]
+///     fn Total(cx: Scope) -> Element {
+///         let cart = Cart::use_(&cx, TOTAL); // Only updated when `total` changes.
+///         let total = *cart.total().read();
+///         cx.render(rsx! { "{total}" })
+///     }
+///
+///     let cart = Cart::share(EDIT);
+///     cart.prices().write().push(5);
+///     let read = Cart::share(Cart::ALL_W);
+///     assert_eq!(*read.total().read(), 12);
+///     cart.transaction(|tx| {
+///         *tx.discount().write() = 2;
+///         tx.prices().write().pop();
+///     });
+///     assert_eq!(*read.total().read(), 5);
+///
+///     let instance = Cart::new_instance();
+///     *instance.discount().write() = 1;
+///     assert_eq!((*instance.total().read(), *read.total().read()), (6, 5));
+///
+///     // The value is computed again once no write guards are held, so its sources can be
+///     // written together.
+///     let (mut prices, mut discount) = (cart.prices().write(), cart.discount().write());
+///     prices.push(1);
+///     *discount = 0;
+///     drop((prices, discount));
+///     assert_eq!(*read.total().read(), 8);
+///     # let (mut prices, mut discount) = (instance.prices().write(), instance.discount().write());
+///     # prices.push(2);
+///     # *discount = 0;
+///     # drop((prices, discount));
+///     # assert_eq!(*instance.total().read(), 9);
+///     # let mut dom = VirtualDom::new(Total);
+///     # let _ = dom.rebuild();
+/// ```
+///
+/// Actions are checked against the fields of the struct, so a typo in an action is reported
+/// where it is made:
+/// ```compile_fail
+///     dioxus_shareables::shareable_struct! {
+///         pub struct GlobalState {
+///             a: usize = 8,
+///         }
+///         action A = W[a] RW[b]; // error: unknown field `b` in action `A`
+///     }
+/// ```
 #[macro_export]
 macro_rules! shareable_struct {
-    (
-        $(#[$meta:meta])*
-        $v:vis struct $Struct:ident {
-            $($fields:tt)*
-        }
-        $($actions:tt)*
-    ) => {
-        $crate::shareable_struct_parse_actions! {
-            remaining_actions: {$($actions)*}
-            vis: [$v]
-            struct: [$Struct]
-            meta: [$(#[$meta])*]
-            fields: {$($fields)*}
-            parsed_actions: []
-        }
-    };
-}
-
-#[doc(hidden)]
-#[macro_export]
-macro_rules! shareable_struct_parse_actions {
-    ( remaining_actions: {$av:vis action $ACTION:ident$(: $ATv:vis $ATy:ident)?$(impl $ATrv:vis $ATr:ident)? = $($r:tt)*}
-      vis: $v:tt
-      struct: $s:tt
-      meta: $m:tt
-      fields: $f:tt
-      parsed_actions: $a:tt
-    ) => {
-        $crate::shareable_struct_parse_action_flags! {
-            remaining_actions: {$($r)*}
-            vis: $v
-            struct: $s
-            meta: $m
-            fields: $f
-            parsed_actions: $a
-            action: [[$ACTION] vis: [$av] type: [$($ATv$ATy)?] trait: [$($ATrv$ATr)?]]
-            w: []
-            rw: []
-            sub: []
-        }
-    };
-    ( remaining_actions: {}
-      vis: $v:tt
-      struct: $s:tt
-      meta: $m:tt
-      fields: $f:tt
-      parsed_actions: $a:tt
-    ) => {
-        $crate::shareable_struct_parse_fields! {
-            remaining_fields: $f
-            vis: $v
-            struct: $s
-            meta: $m
-            standard_fields: []
-            substruct_fields: []
-            actions: $a
-        }
-    };
-}
-
-#[doc(hidden)]
-#[macro_export]
-macro_rules! shareable_struct_parse_action_flags {
-    ( remaining_actions: {W[$($w2:ident),*]$($r:tt)*}
-      vis: $v:tt
-      struct: $s:tt
-      meta: $m:tt
-      fields: $f:tt
-      parsed_actions: $a:tt
-      action: $ad:tt
-      w: [$($w:tt)*]
-      rw: $rw:tt
-      sub: $sub:tt
-    ) => {
-        $crate::shareable_struct_parse_action_flags! {
-            remaining_actions: {$($r)*}
-            vis: $v
-            struct: $s
-            meta: $m
-            fields: $f
-            parsed_actions: $a
-            action: $ad
-            w: [$($w)*$(,$w2)*]
-            rw: $rw
-            sub: $sub
-        }
-    };
-    ( remaining_actions: {RW[$($rw2:ident),*]$($r:tt)*}
-      vis: $v:tt
-      struct: $s:tt
-      meta: $m:tt
-      fields: $f:tt
-      parsed_actions: $a:tt
-      action: $ad:tt
-      w: $w:tt
-      rw: [$($rw:tt)*]
-      sub: $sub:tt
-    ) => {
-        $crate::shareable_struct_parse_action_flags! {
-            remaining_actions: {$($r)*}
-            vis: $v
-            struct: $s
-            meta: $m
-            fields: $f
-            parsed_actions: $a
-            action: $ad
-            w: $w
-            rw: [$($rw)*$(,$rw2)*]
-            sub: $sub
-        }
-    };
-    ( remaining_actions: {|$g:ident[$ga:ident]$($r:tt)*}
-      vis: $v:tt
-      struct: $s:tt
-      meta: $m:tt
-      fields: $f:tt
-      parsed_actions: $a:tt
-      action: $ad:tt
-      w: $w:tt
-      rw: $rw:tt
-      sub: [$($sub:tt)*]
-    ) => {
-        $crate::shareable_struct_parse_action_flags! {
-            remaining_actions: {$($r)*}
-            vis: $v
-            struct: $s
-            meta: $m
-            fields: $f
-            parsed_actions: $a
-            action: $ad
-            w: $w
-            rw: $rw
-            sub: [$($sub)*sub_actions { sub: [$g] actions: [$ga] }]
-        }
-    };
-    ( remaining_actions: {;$($r:tt)*}
-      vis: $v:tt
-      struct: $s:tt
-      meta: $m:tt
-      fields: $f:tt
-      parsed_actions: [$($a:tt)*]
-      action: [[$action:ident]$($ad:tt)*]
-      w: $w:tt
-      rw: $rw:tt
-      sub: $sub:tt
-    ) => {
-        $crate::shareable_struct_parse_actions! {
-            remaining_actions: {$($r)*}
-            vis: $v
-            struct: $s
-            meta: $m
-            fields: $f
-            parsed_actions: [$($a)*
-                action $action {
-                    $($ad)*
-                    w: $w
-                    rw: $rw
-                    sub: $sub
-                }
-            ]
-        }
+    ($($t:tt)*) => {
+        $crate::reexported::shareable_struct! { $crate $($t)* }
     };
 }
 
-#[doc(hidden)]
-#[macro_export]
-macro_rules! shareable_struct_parse_fields {
-    ( remaining_fields: {$fvis:vis $f:ident: $T:ty = $init:expr$(,$($r:tt)*)?}
-      vis: $v:tt
-      struct: $s:tt
-      meta: $m:tt
-      standard_fields: [$($ff:tt)*]
-      substruct_fields: $g:tt
-      actions: $a:tt
-    ) => {
-        $crate::shareable_struct_parse_fields! {
-            remaining_fields: {$($($r)*)?}
-            vis: $v
-            struct: $s
-            meta: $m
-            standard_fields: [$($ff)*field $f { vis: [$fvis] type: [$T] init: [$init] }]
-            substruct_fields: $g
-            actions: $a
-        }
-    };
-    ( remaining_fields: {}
-      vis: $v:tt
-      struct: $s:tt
-      meta: $m:tt
-      standard_fields: $f:tt
-      substruct_fields: $g:tt
-      actions: $a:tt
-    ) => {
-        $crate::shareable_struct_main! {
-            vis: $v
-            struct: $s
-            meta: $m
-            standard_fields: $f
-            substruct_fields: $g
-            actions: $a
-        }
-    };
-    ( remaining_fields: {|$gvis:vis $g:ident: $T:ident$(::$Tc:ident)*$(,$($r:tt)*)?}
-      vis: $v:tt
-      struct: $s:tt
-      meta: $m:tt
-      standard_fields: $f:tt
-      substruct_fields: $gg:tt
-      actions: $a:tt
-    ) => {
-        $crate::shareable_struct_parse_substruct_path! {
-            rest: [$($Tc)*]
-            vis: $v
-            struct: $s
-            meta: $m
-            standard_fields: $f
-            substruct_fields: $gg
-            remaining_fields: {$($($r)*)?}
-            actions: $a
-            substruct_field: [field $g { vis: [$gvis] }]
-            head: [::]
-            tail: $T
-        }
-    };
-}
-
-#[doc(hidden)]
-#[macro_export]
-macro_rules! shareable_struct_parse_substruct_path {
-    ( rest: [$Tn:ident$($Tc:tt)*]
-      vis: $v:tt
-      struct: $s:tt
-      meta: $m:tt
-      standard_fields: $f:tt
-      substruct_fields: $gg:tt
-      remaining_fields: $r:tt
-      actions: $a:tt
-      substruct_field: $g:tt
-      head: [$($h:tt)*]
-      tail: $Tl:ident
-    ) => {
-        $crate::shareable_struct_parse_substruct_path! {
-            rest: [$($Tc)*]
-            vis: $v
-            struct: $s
-            meta: $m
-            standard_fields: $f
-            substruct_fields: $gg
-            remaining_fields: $r
-            actions: $a
-            substruct_field: $g
-            head: [$($h)*$Tl::]
-            tail: $Tn
-        }
-    };
-    ( rest: []
-      vis: $v:tt
-      struct: $s:tt
-      meta: $m:tt
-      standard_fields: $f:tt
-      substruct_fields: [$($gg:tt)*]
-      remaining_fields: $r:tt
-      actions: $a:tt
-      substruct_field: [field $g:ident { vis: [$gvis:vis] }]
-      head: [::$($h:tt)*]
-      tail: $Tl:ident
-    ) => {
-        $crate::reexported::paste! {
-            $crate::shareable_struct_parse_fields! {
-                remaining_fields: $r
-                vis: $v
-                struct: $s
-                meta: $m
-                standard_fields: $f
-                substruct_fields: [$($gg)*field $g { vis: [$gvis] struct: [$($h)*$Tl] actions: [$($h)*[<$Tl Actions>]] as_actions: [$($h)*[<As $Tl Actions>]] }]
-                actions: $a
-            }
-        }
-    };
-}
-
-#[doc(hidden)]
-#[macro_export]
-macro_rules! shareable_struct_main {
-    ( vis: [$v:vis]
-      struct: [$Struct:ident]
-      meta: [$(#[$meta:meta])*]
-      standard_fields: [
-          $(field $f:ident {
-              vis: [$fvis:vis]
-              type: [$fT:ty]
-              init: [$init:expr]
-          })*
-      ]
-      substruct_fields: [
-          $(field $g:ident {
-              vis: [$gvis:vis]
-              struct: [$gT:ty]
-              actions:
-              [$gAT:ty]
-              as_actions: [$AgAT:ty]
-          })*
-      ]
-      actions: [ $(
-          action $ACTION:ident {
-              vis: [$av:vis]
-              type: [$($ATv:vis$ATy:ident)?]
-              trait: [$($ATrv:vis$ATr:ident)?]
-              w: [$(,$w:ident)*]
-              rw: [$(,$rw:ident)*]
-              sub: [$(sub_actions { sub: [$sa:ident] actions: [$saA:ident] })*]
-          }
-      )* ]
-    ) => {
-        $crate::reexported::paste! {
-            $(#[$meta])*
-            $v struct $Struct<__Actions: [<$Struct Actions>] = ()> {
-                $($f: Option<$crate::Shared<$fT, <__Actions as [<$Struct Actions>]>::[<$f:camel Flag>]>>,)*
-                $($g: $gT<<__Actions as [<$Struct Actions>]>::[<$g:camel Actions>]>,)*
-                #[doc(hidden)]
-                __actions_marker: std::marker::PhantomData<__Actions>,
-            }
-            impl<__Actions: [<$Struct Actions>]> $Struct<__Actions> {
-                $v fn share(__a: __Actions) -> Self where __Actions: $crate::r#struct::WriteActions $(, <__Actions as [<$Struct Actions>]>::[<$g:camel Actions>]: $crate::r#struct::WriteActions)* {
-                    #[allow(unused_mut)]
-                    let mut self_ = Self {
-                        $($f: None,)*
-                        $($g: $gT::share([<$Struct Actions>]::[<$g _actions>](&__a)),)*
-                        __actions_marker: std::marker::PhantomData,
-                    };
-                    $(
-                        <__Actions::[<$f:camel Flag>] as $crate::InitType>::share_field(
-                            &mut self_.$f,
-                            <Self as $crate::r#struct::ShareableStruct>::Fields::[<$f:snake:upper>],
-                        );
-                    )*
-                    self_
-                }
-                #[doc(hidden)]
-                $v fn __uninit() -> Self {
-                    Self {
-                        $($f: None,)*
-                        $($g: $gT::__uninit(),)*
-                        __actions_marker: std::marker::PhantomData,
-                    }
-                }
-                #[doc(hidden)]
-                $v fn __init_in<P>(&mut self, cx: &$crate::reexported::Scope<P>) {
-                    $(
-                        <__Actions::[<$f:camel Flag>] as $crate::InitType>::init_field(
-                            cx,
-                            &mut self.$f,
-                            <Self as $crate::r#struct::ShareableStruct>::Fields::[<$f:snake:upper>],
-                        );
-                    )*
-                    $(
-                        self.$g.__init_in(cx);
-                    )*
-                }
-                $v fn use_<'a, P>(cx: &$crate::reexported::Scope<'a, P>, _: __Actions) -> &'a mut Self {
-                    cx.use_hook(|| {
-                        let mut self_ = Self::__uninit();
-                        self_.__init_in(cx);
-                        self_
-                    })
-                }
-                $v fn with_actions<B: [<$Struct Actions>]>(&self, _: B) -> &$Struct<B>
-                where __Actions: [<As $Struct Actions>]<B>
-                {
-                    // SAFETY:
-                    //   * the layout of $Struct<F> does not depend on F.
-                    //   * the [<As $Struct Actions>] trait guarantees that an initialized $Struct<__Actions>
-                    //     has initialized all the fields that should be initialized in $Struct<B>
-                    unsafe { std::mem::transmute(self) }
-                }
-                $($fvis fn $f(&self) -> &$crate::Shared<$fT, <__Actions as [<$Struct Actions>]>::[<$f:camel Flag>]> where <__Actions as [<$Struct Actions>]>::[<$f:camel Flag>]: $crate::Flag {
-                    if let Some($f) = self.$f.as_ref() { $f }
-                    else { unreachable!{} }
-                })*
-                $($gvis fn $g(&self) -> &$gT<<__Actions as [<$Struct Actions>]>::[<$g:camel Actions>]> {
-                    &self.$g
-                })*
-            }
-            #[doc = "Actions on a " $Struct]
-            #[doc = "See [`dioxus_shareables::shareable_struct`] for more info"]
-            /// An actions object describes a collection of field access types you might use
-            /// together.
-            $v trait [<$Struct Actions>]: 'static + Copy {
-                $(type [<$f:camel Flag>]: $crate::InitType;)*
-                $(
-                    type [<$g:camel Actions>]: $gAT;
-                    fn [<$g _actions>](&self) -> Self::[<$g:camel Actions>];
-                )*
-            }
-            #[doc = "Marker trait for allowed conversions between `" [<$Struct Actions>] "` markers."]
-            #[doc = "Implementing this yourself can lead to undefined behavior."]
-            $v trait [<As $Struct Actions>]<B: [<$Struct Actions>]>: [<$Struct Actions>] {}
-            impl<A: [<$Struct Actions>], B: [<$Struct Actions>]> [<As $Struct Actions>]<B> for A
-            where
-                A: 'static
-                    $(+ $crate::r#struct::InitFieldAs<$crate::struct_assoc_type!{$Struct::Fields::$f}, <B as [<$Struct Actions>]>::[<$f:camel Flag>]>)*,
-                $(<A as [<$Struct Actions>]>::[<$g:camel Actions>]: $AgAT<<B as [<$Struct Actions>]>::[<$g:camel Actions>]>,)*
-            {
-            }
-            $(
-                $av const $ACTION: $crate::struct_assoc_type!{$Struct::Actions::$ACTION} = <$Struct as $crate::r#struct::ShareableStruct>::Actions::$ACTION;
-                $($ATv type $ATy = $crate::struct_assoc_type!{$Struct::Actions::$ACTION};)?
-                $(
-                    $ATrv trait $ATr: [<As $Struct Actions>]<$crate::struct_assoc_type!{$Struct::Actions::$ACTION}> {}
-                    impl<T: [<As $Struct Actions>]<$crate::struct_assoc_type!{$Struct::Actions::$ACTION}>> $ATr for T {}
-                )?
-            )*
-            impl<A: [<$Struct Actions>], B: [<As $Struct Actions>]<A>> AsRef<$Struct<A>> for $Struct<B> {
-                fn as_ref(&self) -> &$Struct<A> {
-                    // SAFETY:
-                    //   * the layout of $Struct<F> does not depend on F.
-                    //   * the [<As $Struct Actions>] trait guarantees that an initialized $Struct<B>
-                    //     has initialized all the fields that should be initialized in $Struct<A>
-                    unsafe { std::mem::transmute(self) }
-                }
-            }
-            impl<A: [<$Struct Actions>], B: [<As $Struct Actions>]<A>> AsMut<$Struct<A>> for $Struct<B> {
-                fn as_mut(&mut self) -> &mut $Struct<A> {
-                    // SAFETY:
-                    //   * the layout of $Struct<F> does not depend on F.
-                    //   * the [<As $Struct Actions>] trait guarantees that an initialized $Struct<B>
-                    //     has initialized all the fields that should be initialized in $Struct<A>
-                    unsafe { std::mem::transmute(self) }
-                }
-            }
-            #[allow(dead_code)]
-            const _: () = {
-                $v struct [<$Struct FieldData>];
-                $v struct [<$Struct ActionData>];
-                #[derive(Clone, Copy)]
-                $v struct InitAs<F, A>(F, A);
-
-                impl<__Actions: [<$Struct Actions>]> $crate::r#struct::ShareableStruct for $Struct<__Actions> {
-                    type Fields = [<$Struct FieldData>];
-                    type Actions = [<$Struct ActionData>];
-                }
-
-                $($crate::shareable!{$v [<$Struct FieldShareable $f:camel>]: $fT = $init}
-                    impl $crate::r#struct::FieldOf<$Struct> for [<$Struct FieldShareable $f:camel>] {
-                        type WType = InitAs<[<$Struct FieldShareable $f:camel>], $crate::W>;
-                        type RWType = InitAs<[<$Struct FieldShareable $f:camel>], $crate::RW>;
-                        const W: Self::WType = InitAs([<$Struct FieldShareable $f:camel>], $crate::W);
-                        const RW: Self::RWType = InitAs([<$Struct FieldShareable $f:camel>], $crate::RW);
-                    }
-                    impl $crate::r#struct::WriteActions for InitAs<[<$Struct FieldShareable $f:camel>], $crate::W> {}
-                )*
-                $(
-                    #[derive(Clone, Copy)]
-                    $v struct [<$Struct Substruct $g:camel>];
-                )*
-
-                impl [<$Struct FieldData>] {
-                    $($fvis const [<$f:snake:upper>]: [<$Struct FieldShareable $f:camel>] = [<$Struct FieldShareable $f:camel>];)*
-                }
-                impl [<$Struct ActionData>] {
-                    $(const $ACTION:
-                        (
-                            $(InitAs<[<$Struct FieldShareable $w:camel>], $crate::W>,)*
-                            $(InitAs<[<$Struct FieldShareable $rw:camel>], $crate::RW>,)*
-                            $(InitAs<[<$Struct Substruct $sa:camel>], $crate::struct_assoc_type!{$Struct::Substructs::$sa::Actions::$saA}>,)*
-                        ) = (
-                            $(InitAs([<$Struct FieldShareable $w:camel>], $crate::W),)*
-                            $(InitAs([<$Struct FieldShareable $rw:camel>], $crate::RW),)*
-                            $(InitAs([<$Struct Substruct $sa:camel>], <$crate::struct_assoc_type!{$Struct::Fields::$sa} as $crate::r#struct::ShareableStruct>::Actions::$saA),)*
-                        );
-                    )*
-                }
-                $crate::shareable_struct_init_as_fields!{
-                    remaining_fields: [$($f)*]
-                    struct: [$Struct]
-                    init_as: [InitAs]
-                    prev_fields: []
-                    substruct_fields: [$(field $g { type: [$gAT] })*]
-                }
-                $crate::shareable_struct_init_as_substructs!{
-                    remaining_fields: [$(field $g { type: [$gAT] })*]
-                    struct: [$Struct]
-                    init_as: [InitAs]
-                    prev_fields: []
-                    standard_fields: [$($f)*]
-                }
-                impl<A:
-                    'static + Copy
-                    $(+ $crate::r#struct::InitField<[<$Struct FieldShareable $f:camel>]>)*
-                    $(+ $crate::r#struct::InitSubstruct<[<$Struct Substruct $g:camel>]>)*
-                    > [<$Struct Actions>] for A
-                where $(<A as $crate::r#struct::InitSubstruct<[<$Struct Substruct $g:camel>]>>::Actions: $gAT),* {
-                    $(type [<$f:camel Flag>] = <A as $crate::r#struct::InitField<[<$Struct FieldShareable $f:camel>]>>::Flag;)*
-                    $(
-                        type [<$g:camel Actions>] = <A as $crate::r#struct::InitSubstruct<[<$Struct Substruct $g:camel>]>>::Actions;
-                        fn [<$g _actions>](&self) -> Self::[<$g:camel Actions>] {
-                            <A as $crate::r#struct::InitSubstruct<[<$Struct Substruct $g:camel>]>>::substruct_actions(self)
-                        }
-                    )*
-                }
-
-                $($crate::struct_assoc_type!{impl $Struct::Fields::$f for [<$Struct FieldData>] = [<$Struct FieldShareable $f:camel>]})*
-                $($crate::struct_assoc_type!{impl $Struct::Fields::$g for [<$Struct FieldData>] = $gT})*
-                $($crate::struct_assoc_type!{impl $Struct::Actions::$ACTION for [<$Struct ActionData>] =
-                    (
-                        $(InitAs<[<$Struct FieldShareable $w:camel>], $crate::W>,)*
-                        $(InitAs<[<$Struct FieldShareable $rw:camel>], $crate::RW>,)*
-                        $(InitAs<[<$Struct Substruct $sa:camel>], $crate::struct_assoc_type!{$Struct::Substructs::$sa::Actions::$saA}>,)*
-                    )
-                })*
-            };
-        }
-    };
-}
-
-#[doc(hidden)]
-#[macro_export]
-macro_rules! shareable_struct_init_as_fields {
-    ( remaining_fields: [$f:ident$($r:ident)*]
-      struct: [$Struct:ident]
-      init_as:  [$IA:ident]
-      prev_fields: [$($p:ident)*]
-      substruct_fields: [$(field $g:ident {type: [$gAT:ty]})*]
-    ) => {
-        $crate::reexported::paste! {
-            impl<A: $crate::InitType> $crate::r#struct::InitField<[<$Struct FieldShareable $f:camel>]> for InitAs<[<$Struct FieldShareable $f:camel>], A> {
-                type Flag = A;
-            }
-            $(impl<A: $crate::InitType> $crate::r#struct::InitField<[<$Struct FieldShareable $f:camel>]> for InitAs<[<$Struct FieldShareable $r:camel>], A> {
-                type Flag = ();
-            })*
-            $(impl<A: $crate::InitType> $crate::r#struct::InitField<[<$Struct FieldShareable $f:camel>]> for InitAs<[<$Struct FieldShareable $p:camel>], A> {
-                type Flag = ();
-            })*
-            $(impl<A: $gAT> $crate::r#struct::InitField<[<$Struct FieldShareable $f:camel>]> for InitAs<[<$Struct Substruct $g:camel>], A> {
-                type Flag = ();
-            })*
-        }
-        $crate::shareable_struct_init_as_fields!{
-            remaining_fields: [$($r)*]
-            struct: [$Struct]
-            init_as: [$IA]
-            prev_fields: [$($p)*$f]
-            substruct_fields: [$(field $g {type: [$gAT]})*]
-        }
-    };
-    ( remaining_fields: []
-      struct: $s:tt
-      init_as: $IA:tt
-      prev_fields: $p:tt
-      substruct_fields: $g:tt
-    ) => {};
-}
-
-#[doc(hidden)]
-#[macro_export]
-macro_rules! shareable_struct_init_as_substructs {
-    ( remaining_fields: [field $g:ident {type: [$gAT:ty]}$(field $r:ident {type: [$rAT:ty]})*]
-      struct: [$Struct:ident]
-      init_as:  [$IA:ident]
-      prev_fields: [$(field $p:ident {type: [$pAT:ty]})*]
-      standard_fields: [$($f:ident)*]
-    ) => {
-        $crate::reexported::paste! {
-            impl<A: $gAT> $crate::r#struct::InitSubstruct<[<$Struct Substruct $g:camel>]> for InitAs<[<$Struct Substruct $g:camel>], A> {
-                type Actions = A;
-                fn substruct_actions(&self) -> A {self.1}
-            }
-            $(impl<A: $rAT> $crate::r#struct::InitSubstruct<[<$Struct Substruct $g:camel>]> for InitAs<[<$Struct Substruct $r:camel>], A> {
-                type Actions = ();
-                fn substruct_actions(&self) -> () {}
-            })*
-            $(impl<A: $pAT> $crate::r#struct::InitSubstruct<[<$Struct Substruct $g:camel>]> for InitAs<[<$Struct Substruct $p:camel>], A> {
-                type Actions = ();
-                fn substruct_actions(&self) -> () {}
-            })*
-            $(impl<A: $crate::InitType> $crate::r#struct::InitSubstruct<[<$Struct Substruct $g:camel>]> for InitAs<[<$Struct FieldShareable $f:camel>], A> {
-                type Actions = ();
-                fn substruct_actions(&self) -> () {}
-            })*
-        }
-        $crate::shareable_struct_init_as_substructs!{
-            remaining_fields: [$(field $r {type: [$rAT]})*]
-            struct: [$Struct]
-            init_as:  [$IA]
-            prev_fields: [$(field $p {type: [$pAT:ty]})*field $g {type: [$gAT]}]
-            standard_fields: [$($f)*]
-        }
-    };
-    ( remaining_fields: []
-      struct: $s:tt
-      init_as: $IA:tt
-      prev_fields: $p:tt
-      standard_fields: $g:tt
-    ) => {};
-}
-
 #[doc(hidden)]
 #[macro_export]
 macro_rules! struct_assoc_type {
-    ($Struct:ident::Actions::$action:ident) => {
-        $crate::reexported::paste! {
-            $crate::struct_assoc_type! {
-                @(<<$Struct as $crate::r#struct::ShareableStruct>::Actions as )([<Action $action:camel>])(>::Type)
-            }
-        }
-    };
-    ($Struct:ident::Fields::$field:ident) => {
-        $crate::reexported::paste! {
-            $crate::struct_assoc_type! {
-                @(<<$Struct as $crate::r#struct::ShareableStruct>::Fields as )([<Field $field:camel>])(>::Type)
-            }
-        }
-    };
-    ($Struct:ident::Substructs::$field:ident::Actions::$action:ident) => {
-        $crate::reexported::paste! {
-            $crate::struct_assoc_type! {
-                @(<<<<$Struct as $crate::r#struct::ShareableStruct>::Fields as )([<Field $field:camel>])(>::Type)
-                @(as $crate::r#struct::ShareableStruct>::Actions as)([<Action $action>])(>::Type)
-            }
-        }
-    };
-    (impl $Struct:ident::Actions::$action:ident for $T:ty = $($what:tt)*) => {
-        $crate::reexported::paste! {
-            $crate::struct_assoc_type! {
-                @(impl)([<Action $action:camel>])(for $T { type Type = $($what)*; })
-            }
-        }
+    ($($t:tt)*) => {
+        $crate::reexported::struct_assoc_type! { $crate $($t)* }
     };
-    (impl $Struct:ident::Fields::$field:ident for $T:ty = $($what:tt)*) => {
-        $crate::reexported::paste! {
-            $crate::struct_assoc_type! {
-                @(impl)([<Field $field:camel>])( for $T { type Type = $($what)*; })
-            }
-        }
-    };
-    ($(@($($before:tt)*)($($x:tt)*)($($after:tt)*))*) => {
-        $($($before)*$crate::r#struct::AssocType<
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 0)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 1)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 2)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 3)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 4)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 5)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 6)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 7)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 8)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 9)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 10)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 11)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 12)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 13)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 14)},
-            {$crate::r#struct::seg_str(stringify!{$($x)*}, 15)},
-        >$($after)*)*
-    }
 }
 
 /// Get actions on a struct.
@@ -990,96 +858,10 @@ macro_rules! struct_assoc_type {
 /// For example `dioxus_shareables::struct_actions!(GlobalState<{W[a] RW[b]}>)` gives the correct
 /// type for a `dioxus_shareables` struct with write access to field `a` and read-write access to
 /// field `b`, and `dioxus_shareables::struct_actions!(GlobalState(W[a] RW[b]))` gives a
-/// corresponding expression.
+/// corresponding expression. Computed fields are given with `R[..]`.
 #[macro_export]
 macro_rules! struct_actions {
-    ($Struct:ident$(::$Struct_:ident)*<{$($ty:tt)*}>) => {
-        $crate::struct_actions_! {
-            unparsed: [$($ty)*]
-            produce: ty
-            struct: [$Struct$(::$Struct_)*]
-        }
-    };
-    ($Struct:ident$(::$Struct_:ident)*($($ty:tt)*)) => {
-        $crate::struct_actions_! {
-            unparsed: [$($ty)*]
-            produce: expr
-            struct: [$Struct$(::$Struct_)*]
-        }
-    };
-}
-#[doc(hidden)]
-#[macro_export]
-macro_rules! struct_actions_ {
-    (
-        unparsed: []
-        produce: $t:tt
-        struct: [$Struct:path]
-    ) => {
-        ()
-    };
-    (
-        unparsed: [W[$($w:ident)*]$($r:tt)*]
-        produce: ty
-        struct: [$($Struct:tt)*]
-    ) => {
-        (
-            ($(
-                <$crate::struct_assoc_type!($Struct::Fields::$w) as $crate::r#struct::FieldOf<$Struct>>::WType
-            ),*),
-            $crate::struct_actions_! {
-                unparsed: [$($r)*]
-                produce: ty
-                struct: [$($Struct)*]
-            }
-        )
-    };
-    (
-        unparsed: [RW[$($w:ident)*]$($r:tt)*]
-        produce: ty
-        struct: [$($Struct:tt)*]
-    ) => {
-        (
-            ($(
-                <$crate::struct_assoc_type!($Struct::Fields::$w) as $crate::r#struct::FieldOf<$Struct>>::RWType
-            ),*),
-            $crate::struct_actions_! {
-                unparsed: [$($r)*]
-                produce: ty
-                struct: [$($Struct)*]
-            }
-        )
-    };
-    (
-        unparsed: [W[$($w:ident)*]$($r:tt)*]
-        produce: expr
-        struct: [$($Struct:tt)*]
-    ) => {
-        (
-            ($(
-                <$crate::struct_assoc_type!($Struct::Fields::$w) as $crate::r#struct::FieldOf<$Struct>>::W
-            ),*),
-            $crate::struct_actions_! {
-                unparsed: [$($r)*]
-                produce: expr
-                struct: [$($Struct)*]
-            }
-        )
-    };
-    (
-        unparsed: [RW[$($w:ident)*]$($r:tt)*]
-        produce: expr
-        struct: [$($Struct:tt)*]
-    ) => {
-        (
-            ($(
-                <$crate::struct_assoc_type!($Struct::Fields::$w) as $crate::r#struct::FieldOf<$Struct>>::RW
-            ),*),
-            $crate::struct_actions_! {
-                unparsed: [$($r)*]
-                produce: expr
-                struct: [$($Struct)*]
-            }
-        )
+    ($($t:tt)*) => {
+        $crate::reexported::struct_actions! { $crate $($t)* }
     };
 }